@@ -0,0 +1,95 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use openbrush::contracts::traits::psp22::PSP22Error;
+
+/// The part of PSP22 the governor reads its governance token through.
+#[ink::trait_definition]
+pub trait PSP22 {
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: ink::primitives::AccountId) -> u128;
+}
+
+#[ink::trait_definition]
+pub trait PSP22Burnable {
+    #[ink(message)]
+    fn burn(
+        &mut self,
+        account: ink::primitives::AccountId,
+        amount: u128,
+    ) -> Result<(), PSP22Error>;
+}
+
+/// Governance token double for the governor's e2e tests. Every account holds the
+/// whole supply, and burning can call back into the governor's `rage_quit`, the
+/// way a malicious token would.
+#[ink::contract]
+pub mod callback_token {
+    use super::PSP22Error;
+    use ink::{
+        env::{
+            call::{
+                build_call,
+                ExecutionInput,
+                Selector,
+            },
+            DefaultEnvironment,
+        },
+        prelude::string::String,
+    };
+
+    pub const TOTAL_SUPPLY: Balance = 1_000;
+
+    #[ink(storage)]
+    pub struct CallbackToken {
+        /// Whether `burn` calls back into its caller.
+        call_back: bool,
+    }
+
+    impl CallbackToken {
+        #[ink(constructor)]
+        pub fn new(call_back: bool) -> Self {
+            Self { call_back }
+        }
+    }
+
+    impl super::PSP22 for CallbackToken {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            TOTAL_SUPPLY
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, _owner: AccountId) -> Balance {
+            TOTAL_SUPPLY
+        }
+    }
+
+    impl super::PSP22Burnable for CallbackToken {
+        /// Burns nothing. When calling back, fails unless the governor accepted the
+        /// callback.
+        #[ink(message)]
+        fn burn(
+            &mut self,
+            _account: AccountId,
+            amount: Balance,
+        ) -> Result<(), PSP22Error> {
+            if !self.call_back {
+                return Ok(())
+            }
+            // Governor errors carry no fields, so their index stands in for them.
+            build_call::<DefaultEnvironment>()
+                .call(self.env().caller())
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("rage_quit")))
+                        .push_arg(amount),
+                )
+                .returns::<Result<Balance, u8>>()
+                .invoke()
+                .map(|_| ())
+                .map_err(|_| PSP22Error::Custom(String::from("callback refused")))
+        }
+    }
+}
@@ -1,27 +1,440 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+/// Computes voting weight for a governor configured with a strategy contract, in
+/// place of the governance token balance.
+#[ink::trait_definition]
+pub trait VotingStrategy {
+    /// Weight `account` casts on a proposal snapshotted at `snapshot`.
+    #[ink(message)]
+    fn weight(&self, account: ink::primitives::AccountId, snapshot: u64) -> u128;
+
+    /// Sum of the weight all accounts could cast at `snapshot`, which quorum and
+    /// the weight cap are measured against.
+    #[ink(message)]
+    fn total_weight(&self, snapshot: u64) -> u128;
+}
+
+/// Exchange the governor trades treasury assets on, configured with
+/// `GovernorParam::DexRouter`. `None` stands for the native currency.
+#[ink::trait_definition]
+pub trait DexRouter {
+    /// Swaps `amount_in` of `asset_in`, attached as value when native or pulled
+    /// with an allowance otherwise, for at least `min_amount_out` of `asset_out`
+    /// sent to `to`. Returns the amount sent.
+    #[ink(message, payable)]
+    fn swap(
+        &mut self,
+        asset_in: Option<ink::primitives::AccountId>,
+        asset_out: Option<ink::primitives::AccountId>,
+        amount_in: u128,
+        min_amount_out: u128,
+        to: ink::primitives::AccountId,
+    ) -> u128;
+}
+
+/// Contract the governor stakes idle native funds with, configured with
+/// `GovernorParam::StakingAdapter`.
+#[ink::trait_definition]
+pub trait StakingAdapter {
+    /// Stakes the attached value for the caller.
+    #[ink(message, payable)]
+    fn stake(&mut self);
+
+    /// Unstakes `amount` of the caller's stake, sending it back together with the
+    /// rewards it accrued. Returns the amount sent.
+    #[ink(message)]
+    fn unstake(&mut self, amount: u128) -> u128;
+}
+
+/// Prices treasury assets in a common reference unit, configured with
+/// `GovernorParam::PriceOracle`.
+#[ink::trait_definition]
+pub trait PriceOracle {
+    /// Reference units one unit of `asset` is worth, times `PRICE_SCALE`. A `None`
+    /// asset is the native currency. Returns `None` for assets without a price.
+    #[ink(message)]
+    fn price(&self, asset: Option<ink::primitives::AccountId>) -> Option<u128>;
+}
+
+/// KYC or compliance registry restricting who may take part, configured with
+/// `GovernorParam::ComplianceRegistry`.
+#[ink::trait_definition]
+pub trait ComplianceRegistry {
+    /// Whether `account` passed the registry's checks.
+    #[ink(message)]
+    fn is_verified(&self, account: ink::primitives::AccountId) -> bool;
+}
+
 #[ink::contract]
 pub mod dao {
-    use ink::storage::Mapping;
-    use openbrush::contracts::traits::psp22::*;
+    #[cfg(not(test))]
+    use ink::env::{
+        call::{
+            build_call,
+            ExecutionInput,
+            Selector,
+        },
+        DefaultEnvironment,
+    };
+    use ink::{
+        prelude::{
+            string::String,
+            vec,
+            vec::Vec,
+        },
+        storage::{
+            Lazy,
+            Mapping,
+        },
+    };
+    use openbrush::contracts::traits::psp22::PSP22Error;
+    #[cfg(not(test))]
+    use openbrush::contracts::traits::psp34::Id;
+    #[cfg(not(test))]
+    use scale::Output;
     use scale::{
         Decode,
         Encode,
     };
 
-    #[derive(Encode, Decode)]
-    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub type ProposalId = u32;
+    pub type TrackId = u8;
+    pub type DistributionId = u32;
+
+    /// Block timestamps are expressed in milliseconds.
+    pub const ONE_MINUTE: u64 = 60 * 1000;
+
+    /// Upper bound on the accounts delegating to a single delegate, which keeps the
+    /// cost of a delegate's `vote` bounded.
+    pub const MAX_DELEGATORS: usize = 64;
+
+    /// Longest delegation chain that is followed, e.g. `A -> B -> C` is two hops.
+    pub const MAX_DELEGATION_HOPS: u32 = 4;
+
+    /// Longest vote-escrow lock, in minutes (four years). A lock this long weighs
+    /// its full amount.
+    pub const MAX_VOTE_LOCK: u64 = 4 * 365 * 24 * 60;
+
+    /// Most options a multi-choice proposal may offer.
+    pub const MAX_CHOICES: usize = 32;
+
+    /// Denominator of basis-point parameters, i.e. 100%.
+    pub const BASIS_POINTS: u16 = 10_000;
+
+    /// Most extra tokens that may add to a voter's weight.
+    pub const MAX_WEIGHT_SOURCES: usize = 8;
+
+    /// Most PSP22 assets the treasury registry holds.
+    pub const MAX_ASSETS: usize = 16;
+
+    /// Most entries `get_ledger` or `get_donors` return at once.
+    pub const MAX_LEDGER_PAGE: u32 = 64;
+
+    /// Most vesting grants an account can hold at once.
+    pub const MAX_VESTING_GRANTS: usize = 16;
+
+    /// Most members of the co-signing committee.
+    pub const MAX_SIGNERS: usize = 16;
+
+    /// Most members of the council.
+    pub const MAX_COUNCIL_MEMBERS: usize = 16;
+
+    /// Fixed-point scale of `PriceOracle` prices.
+    pub const PRICE_SCALE: u128 = 1_000_000_000_000;
+
+    /// Most contribution thresholds receipt NFTs are tiered by.
+    pub const MAX_RECEIPT_TIERS: usize = 8;
+
+    /// Quorum in basis points, and percent of for votes, a `Sunset` proposal needs
+    /// at least.
+    pub const SUNSET_QUORUM: u16 = 7_500;
+    pub const SUNSET_APPROVAL_THRESHOLD: u8 = 90;
+
+    /// Longest objection window or kill switch delay, in minutes (one year).
+    pub const MAX_SAFEGUARD_DELAY: u64 = 365 * 24 * 60;
+
+    /// Layout version of the governor's storage, see `migrate`.
+    pub const STORAGE_VERSION: u32 = 1;
+
+    /// How token balances turn into vote weight.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum CountingMode {
+        /// One token, one vote.
+        Linear,
+        /// Weight is the integer square root of the balance, which limits the
+        /// influence of large holders.
+        Quadratic,
+        /// Weight comes from tokens locked with `lock` instead of the balance,
+        /// scaled by the remaining lock time.
+        VoteEscrow,
+        /// One vote per account owning a membership NFT of the given PSP34
+        /// collection, whatever its token balance. PSP34 keeps no history, so
+        /// ownership is read when the vote is cast.
+        Membership(AccountId),
+    }
+
+    impl CountingMode {
+        /// Weight of `balance` tokens, or of the whole supply for vote-escrow
+        /// counting, where a maximal lock weighs its amount. For membership
+        /// counting `balance` is a number of NFTs.
+        fn weight(self, balance: Balance) -> Balance {
+            match self {
+                CountingMode::Linear | CountingMode::VoteEscrow => balance,
+                CountingMode::Quadratic => isqrt(balance),
+                CountingMode::Membership(_) => balance.min(1),
+            }
+        }
+    }
+
+    /// Largest integer whose square does not exceed `value`.
+    fn isqrt(value: Balance) -> Balance {
+        if value < 2 {
+            return value
+        }
+        let mut root = value / 2 + 1;
+        let mut next = (root + value / root) / 2;
+        while next < root {
+            root = next;
+            next = (root + value / root) / 2;
+        }
+        root
+    }
+
+    /// Commitment to keep the voted tokens locked after `vote_end` for a number
+    /// of conviction periods, in exchange for a weight multiplier.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum Conviction {
+        /// 1x weight, locked for one period.
+        Locked1x,
+        /// 2x weight, locked for two periods.
+        Locked2x,
+        /// 4x weight, locked for four periods.
+        Locked4x,
+    }
+
+    impl Conviction {
+        fn multiplier(self) -> Balance {
+            match self {
+                Conviction::Locked1x => 1,
+                Conviction::Locked2x => 2,
+                Conviction::Locked4x => 4,
+            }
+        }
+
+        fn lock_periods(self) -> u64 {
+            self.multiplier() as u64
+        }
+    }
+
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
     pub enum VoteType {
-        // to implement
+        Against,
+        For,
+        /// Counts toward quorum but not toward approval.
+        Abstain,
+        /// Divides the voter's weight between both sides, e.g. for a custodian
+        /// voting on behalf of several clients. Any weight left over is not cast.
+        Split {
+            for_weight: Balance,
+            against_weight: Balance,
+        },
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ProposalState {
+        /// Voting has not opened yet.
+        Pending,
+        /// Votes are being accepted.
+        Active,
+        /// Voting ended without reaching quorum or with more votes against.
+        Defeated,
+        /// Voting ended in favour; the proposal can be queued.
+        Succeeded,
+        /// Waiting for the timelock delay to elapse before execution.
+        Queued,
+        Executed,
+        /// Succeeded but can no longer be executed.
+        Expired,
+        Canceled,
     }
 
     #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum GovernorError {
-        // to implement
+        AmountShouldNotBeZero,
+        DurationError,
+        ProposalNotFound,
+        ProposalAlreadyExecuted,
+        VotePeriodEnded,
+        AlreadyVoted,
+        VotePeriodNotEnded,
+        QuorumNotReached,
+        /// A native transfer out of the treasury failed.
+        TransferError,
+        ProposalNotAccepted,
+        OnlyProposer,
+        VotingAlreadyStarted,
+        ProposalCanceled,
+        ProposalExpired,
+        ProposalNotQueued,
+        ProposalAlreadyQueued,
+        TimelockNotElapsed,
+        VoteNotStarted,
+        DurationTooShort,
+        DurationTooLong,
+        EmptyProposal,
+        InsufficientTokenBalance,
+        TokenTransferFailed,
+        InvalidProposalKind,
+        InvalidParameter,
+        UpgradeFailed,
+        NoMigrationPending,
+        /// Storage was written by a newer version of the governor.
+        UnsupportedVersion,
+        RecipientBlacklisted,
+        /// Only members may propose and vote while the governor is members-only.
+        NotMember,
+        /// The compliance registry has not verified the account.
+        NotVerified,
+        InvalidSchedule,
+        ScheduleNotFound,
+        NotRecipient,
+        NothingToClaim,
+        MilestoneGrantNotFound,
+        AllMilestonesReleased,
+        IncorrectBond,
+        BondAlreadySettled,
+        BelowProposalThreshold,
+        ProposalCooldownActive,
+        TooManyActiveProposals,
+        SelfDelegation,
+        NotDelegated,
+        TooManyDelegators,
+        VotingPowerDelegated,
+        DelegationCycle,
+        DelegationChainTooLong,
+        NoVoteToRetract,
+        ConvictionAlreadyLocked,
+        NoConvictionLock,
+        TokensStillLocked,
+        NoVoteLock,
+        SplitExceedsWeight,
+        ApprovalThresholdNotMet,
+        ConvictionDisabled,
+        EarlyFinalizationUnsupported,
+        OutcomeNotDecided,
+        InvalidNonce,
+        InvalidSignature,
+        InvalidChoice,
+        EpochNotSettled,
+        EpochSettled,
+        JoiningDisabled,
+        VotesPending,
+        AssetAlreadyRegistered,
+        AssetNotRegistered,
+        TooManyAssets,
+        InsufficientFunds,
+        DistributionNotFound,
+        DividendAlreadyClaimed,
+        NotAnInvestment,
+        StreamNotFound,
+        NotOnPayroll,
+        AlreadyOnPayroll,
+        BudgetExceeded,
+        EscrowNotFound,
+        EscrowSettled,
+        ReleaseNotDefeated,
+        NoDexRouter,
+        SwapFailed,
+        SlippageExceeded,
+        ReceiptMintFailed,
+        RoundNotFound,
+        RoundClosed,
+        HardCapExceeded,
+        RoundNotFailed,
+        NothingToRefund,
+        VetoWindowClosed,
+        Paused,
+        MissingRole,
+        NotPendingAdmin,
+        /// The caller is not allowed to call this message: not the admin, the
+        /// guardian or the governor itself, as the message requires.
+        Unauthorized,
+        /// Open proposals, locks, fundraising deposits or dividends still depend on
+        /// the current governance token.
+        GovernanceTokenInUse,
+        ReentrantCall,
+        ArithmeticOverflow,
+        CrossContractCallFailed,
+        TooManyGrants,
+        GrantNotFound,
+        NotSigner,
+        AlreadySigned,
+        SignaturesMissing,
+        NotCouncilMember,
+        /// The proposal pays out more than the council may fast-track, or than
+        /// its track allows.
+        ProposalTooLarge,
+        TrackNotFound,
+        ObjectionWindowOpen,
+        ObjectionWindowClosed,
+        AlreadyObjected,
+        /// The DAO was dissolved by a `Sunset` proposal.
+        Terminated,
+        /// The guardian's kill switch took effect, see `arm_kill_switch`.
+        KillSwitchEngaged,
+        KillSwitchNotArmed,
+        NoStakingAdapter,
+        StakingFailed,
+        InsufficientStake,
+        /// The contract called by a `GenericCall` proposal failed or reverted.
+        CallFailed,
     }
 
-    #[derive(Encode, Decode)]
+    impl From<PSP22Error> for GovernorError {
+        fn from(error: PSP22Error) -> Self {
+            match error {
+                PSP22Error::InsufficientBalance => {
+                    GovernorError::InsufficientTokenBalance
+                }
+                _ => GovernorError::TokenTransferFailed,
+            }
+        }
+    }
+
+    /// Native funds paid out of the treasury when a proposal is executed.
+    #[derive(Encode, Decode, Clone)]
     #[cfg_attr(
         feature = "std",
         derive(
@@ -32,11 +445,13 @@ pub mod dao {
             ink::storage::traits::StorageLayout
         )
     )]
-    pub struct Proposal {
-        // to implement
+    pub struct Transfer {
+        pub to: AccountId,
+        pub amount: Balance,
     }
 
-    #[derive(Encode, Decode, Default)]
+    /// PSP22 tokens held by the governor, paid out when a proposal is executed.
+    #[derive(Encode, Decode, Clone)]
     #[cfg_attr(
         feature = "std",
         derive(
@@ -47,75 +462,7318 @@ pub mod dao {
             ink::storage::traits::StorageLayout
         )
     )]
-    pub struct ProposalVote {
-        // to implement
+    pub struct TokenTransfer {
+        pub asset: AccountId,
+        pub to: AccountId,
+        pub amount: Balance,
     }
 
-    #[ink(storage)]
-    pub struct Governor {
-        // to implement
+    /// Call into another contract made by the governor when a proposal is executed.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ContractCall {
+        pub callee: AccountId,
+        pub selector: [u8; 4],
+        /// SCALE encoded arguments, appended to the selector as they are.
+        pub input: Vec<u8>,
+        pub transferred_value: Balance,
     }
 
-    impl Governor {
-        #[ink(constructor, payable)]
-        pub fn new(governance_token: AccountId, quorum: u8) -> Self {
-            unimplemented!()
-        }
+    /// What a proposal does once executed. New kinds are appended at the end so
+    /// the encoding of stored proposals stays valid.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum ProposalKind {
+        /// Native treasury payouts, executed atomically.
+        NativeTransfer(Vec<Transfer>),
+        /// PSP22 payouts from the assets held by the governor.
+        TokenTransfer(Vec<TokenTransfer>),
+        /// Call into another contract, e.g. the governance token.
+        GenericCall(ContractCall),
+        /// Updates the governor's own parameters.
+        ParamChange(Vec<GovernorParam>),
+        /// Replaces the governor's code. The new code finishes the upgrade in
+        /// `migrate`.
+        Upgrade(Hash),
+        /// Authorises a payout the recipient claims once per period.
+        Recurring(RecurringPayout),
+        /// Funding split into tranches, each released by its own follow-up vote.
+        Milestones(MilestoneGrant),
+        /// Confirms the next milestone of an executed `Milestones` proposal and
+        /// pays its tranche.
+        ReleaseMilestone(ProposalId),
+        /// Picks one of several named payouts, see `vote_choice`.
+        MultiChoice(MultiChoice),
+        /// Sets treasury funds aside for the voters of an epoch, see
+        /// `claim_rewards`.
+        FundRewards(RewardFunding),
+        /// Distributes native treasury profits to token holders, see
+        /// `claim_dividend`.
+        Distribute(Balance),
+        /// Native grant vesting over time, see `withdraw_stream`.
+        Stream(StreamPayout),
+        /// Stops an executed `Stream` proposal, returning the unvested rest to the
+        /// treasury.
+        CancelStream(ProposalId),
+        /// Puts a contributor on the payroll, paid like a recurring payout but
+        /// claimed with `claim_salary`.
+        Hire(RecurringPayout),
+        /// Ends a contributor's salary, paying the periods already started.
+        Dismiss(AccountId),
+        /// Earmarks a payout inside the governor until a `ReleaseEscrow` vote.
+        Escrow(Transfer),
+        /// Pays out the funds of an executed `Escrow` proposal. If this vote fails
+        /// they can be refunded with `refund_escrow`.
+        ReleaseEscrow(ProposalId),
+        /// Trades treasury assets on the configured DEX router.
+        Swap(Swap),
+        /// Stakes idle native funds with the configured staking adapter.
+        Stake(Balance),
+        /// Unstakes funds from the staking adapter, adding what they earned to the
+        /// treasury.
+        Unstake(Balance),
+        /// Sweeps the treasury's native funds, unlocked governance tokens and
+        /// registered assets to a recovery account. Decided under the stricter
+        /// `EmergencyTerms`.
+        EmergencyWithdrawal(AccountId),
+        /// Dissolves the DAO: no further proposals are accepted and the native
+        /// treasury is left to holders to claim pro rata, see `claim_dividend`.
+        Sunset,
+        /// Buys governance tokens with native funds and burns them.
+        Buyback(Buyback),
+        /// Opens a fundraising round minting governance tokens to contributors.
+        OpenRound(RoundTerms),
+        /// Grant vesting after a cliff, see `claim_vested`.
+        Vesting(VestingGrant),
+        /// Stops an executed `Vesting` or `Stream` grant, keeping the unvested rest
+        /// in the treasury.
+        Clawback(ProposalId),
+    }
 
-        #[ink(message)]
-        pub fn propose(
-            &mut self,
-            to: AccountId,
-            amount: Balance,
-            duration: u64,
-        ) -> Result<(), GovernorError> {
-            unimplemented!()
-        }
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct MilestoneGrant {
+        pub to: AccountId,
+        /// Tranche paid for each milestone, in order.
+        pub amounts: Vec<Balance>,
+    }
 
-        #[ink(message)]
-        pub fn vote(
-            &mut self,
-            proposal_id: ProposalId,
-            vote: VoteType,
-        ) -> Result<(), GovernorError> {
-            unimplemented!()
-        }
+    /// An approved milestone grant and how many of its tranches were released.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct MilestoneProgress {
+        pub grant: MilestoneGrant,
+        pub released: u32,
+    }
 
-        #[ink(message)]
-        pub fn execute(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
-            unimplemented!()
-        }
+    /// How the winning option of a multi-choice proposal is selected.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum WinnerRule {
+        /// The option ranked first by the most weight wins.
+        Plurality,
+        /// Instant runoff: the weakest option is eliminated and its ballots move
+        /// to their next choice until one option holds a majority.
+        RankedChoice,
+    }
 
-        // used for test
-        #[ink(message)]
-        pub fn now(&self) -> u64 {
-            self.env().block_timestamp()
-        }
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ChoiceOption {
+        pub name: String,
+        /// Paid out if this option wins.
+        pub payout: Transfer,
     }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct MultiChoice {
+        pub options: Vec<ChoiceOption>,
+        pub rule: WinnerRule,
+    }
 
-        fn create_contract(initial_balance: Balance) -> Governor {
-            let accounts = default_accounts();
-            set_sender(accounts.alice);
-            set_balance(contract_id(), initial_balance);
-            Governor::new(AccountId::from([0x01; 32]), 50)
-        }
+    /// Options of a multi-choice proposal in the voter's order of preference.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ChoiceBallot {
+        pub ranking: Vec<u8>,
+        pub weight: Balance,
+    }
 
-        fn contract_id() -> AccountId {
-            ink::env::test::callee::<ink::env::DefaultEnvironment>()
-        }
+    /// Grant of `amount` per `period` minutes, paid `periods` times.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct RecurringPayout {
+        pub to: AccountId,
+        pub amount: Balance,
+        pub period: u64,
+        pub periods: u32,
+    }
 
-        fn default_accounts(
-        ) -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
-            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
-        }
+    /// Treasury funds added to the participation rewards of a voting epoch.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct RewardFunding {
+        pub epoch: u64,
+        pub amount: Balance,
+    }
 
-        fn set_sender(sender: AccountId) {
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(sender);
-        }
+    /// Native profits paid out to governance token holders, pro rata to their
+    /// balance at `snapshot`, the snapshot of the proposal that declared it.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Distribution {
+        pub proposal_id: ProposalId,
+        pub amount: Balance,
+        /// Governance token supply at `snapshot`.
+        pub total_supply: Balance,
+        pub snapshot: u64,
+    }
+
+    /// Terms of a fundraising round opened by an `OpenRound` proposal.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct RoundTerms {
+        /// Least the round must raise to succeed.
+        pub soft_cap: Balance,
+        /// Most the round accepts; reaching it closes the round.
+        pub hard_cap: Balance,
+        /// Governance tokens minted per unit deposited.
+        pub rate: Balance,
+        /// Minutes the round stays open.
+        pub duration: u64,
+    }
+
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Round {
+        pub terms: RoundTerms,
+        pub deadline: Timestamp,
+        pub raised: Balance,
+    }
+
+    impl Round {
+        pub fn is_open(&self, now: Timestamp) -> bool {
+            now <= self.deadline && self.raised < self.terms.hard_cap
+        }
+    }
+
+    /// Deposits of a contributor to a round and the tokens minted for them.
+    #[derive(Encode, Decode, Clone, Copy, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct RoundContribution {
+        pub deposited: Balance,
+        pub minted: Balance,
+    }
+
+    /// What happens to budget left unspent at the end of an epoch.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum CarryOver {
+        /// Every epoch starts with exactly the cap.
+        None,
+        /// Unspent budget accumulates without limit.
+        Full,
+        /// Unspent budget accumulates up to this much on top of the cap.
+        UpTo(Balance),
+    }
+
+    /// Payout size from which a proposal is a large payout. With a price oracle
+    /// set, amounts are valued in its reference unit and the treasury is its
+    /// total assets under management.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum PayoutThreshold {
+        Absolute(Balance),
+        /// Basis points of the treasury balance at proposal creation.
+        Treasury(u16),
+    }
+
+    /// Stricter vote requirements of proposals paying out more than `threshold`.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct LargePayout {
+        pub threshold: PayoutThreshold,
+        /// Percent of for votes required, if above the governor's.
+        pub approval_threshold: u8,
+        /// Quorum in basis points required, if above the governor's.
+        pub quorum: u16,
+    }
+
+    /// Vote requirements and timelock of `EmergencyWithdrawal` proposals.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct EmergencyTerms {
+        /// Quorum in basis points required, if above the governor's.
+        pub quorum: u16,
+        /// Percent of for votes required, if above the governor's.
+        pub approval_threshold: u8,
+        /// Minutes queued before execution, if below the governor's timelock.
+        pub timelock_delay: u64,
+    }
+
+    /// Quorum, thresholds and timings every proposal is governed by. Durations are
+    /// in minutes.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct GovernanceConfig {
+        /// Basis points of the total weight at the snapshot that must vote.
+        pub quorum: u16,
+        /// Quorum reached once a vote has been open for `quorum_decay` minutes.
+        pub quorum_floor: u16,
+        /// Minutes over which the quorum decays linearly to `quorum_floor`. Zero
+        /// keeps it fixed.
+        pub quorum_decay: u64,
+        /// Governance tokens an account must hold to submit a proposal.
+        pub proposal_threshold: Balance,
+        /// Percentage of the For and Against weight that must be For.
+        pub approval_threshold: u8,
+        /// Default minutes between proposal creation and `vote_start`.
+        pub voting_delay: u64,
+        /// Bounds on the voting period of a proposal.
+        pub min_duration: u64,
+        pub max_duration: u64,
+        /// Minutes after `vote_end` (or after the timelock, once queued) during
+        /// which a passed proposal can be executed.
+        pub grace_period: u64,
+        /// Minutes a queued proposal must wait before it can be executed.
+        pub timelock_delay: u64,
+    }
+
+    /// Referendum track, e.g. small spends, big spends or admin changes, with
+    /// its own vote requirements. Proposals keep the track as it was when they
+    /// were submitted.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Track {
+        pub id: TrackId,
+        /// In basis points, replacing the governor's quorum.
+        pub quorum: u16,
+        /// Percent of for votes required, replacing the governor's.
+        pub approval_threshold: u8,
+        /// Minutes proposals on the track are open for votes.
+        pub voting_period: u64,
+        pub timelock_delay: u64,
+        /// Most a proposal on the track may pay out, if limited.
+        pub max_spend: Option<Balance>,
+    }
+
+    /// Elected council able to shorten the vote on proposals paying out at most
+    /// `max_amount`, valued like large payouts.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Council {
+        pub members: Vec<AccountId>,
+        /// Approvals of current members needed to fast-track a proposal.
+        pub required: u8,
+        pub max_amount: Balance,
+        /// Minutes a fast-tracked proposal stays open for votes.
+        pub voting_period: u64,
+    }
+
+    /// Committee that must co-sign the execution of payouts worth more than
+    /// `threshold`, valued like large payouts.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct CoSigning {
+        pub signers: Vec<AccountId>,
+        /// Signatures of current signers needed to execute.
+        pub required: u8,
+        pub threshold: Balance,
+    }
+
+    /// Trade of treasury assets on the DEX router. `None` is the native currency.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Swap {
+        pub asset_in: Option<AccountId>,
+        pub asset_out: Option<AccountId>,
+        pub amount_in: Balance,
+        /// Least the treasury must receive, or the execution fails.
+        pub min_amount_out: Balance,
+    }
+
+    /// Governance tokens the treasury buys on the DEX router and burns, along with
+    /// tokens members contributed to it.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Buyback {
+        /// Native funds spent on the router, if any.
+        pub amount: Balance,
+        /// Least amount of tokens the spent funds must buy.
+        pub min_tokens_out: Balance,
+        /// Governance tokens already held by the treasury to burn as well.
+        pub contributed: Balance,
+    }
+
+    /// Native funds earmarked by an executed `Escrow` proposal until a release
+    /// vote pays them out or fails.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Escrow {
+        pub transfer: Transfer,
+        /// Released to the recipient or refunded to the treasury.
+        pub settled: bool,
+    }
+
+    /// Native funds paid out by proposals and returned to the treasury for them.
+    #[derive(Encode, Decode, Clone, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Investment {
+        pub disbursed: Balance,
+        pub returned: Balance,
+    }
+
+    /// Permissions granted with `grant_role`. A role nobody holds is open to
+    /// everyone, so the governor stays permissionless until roles are handed out.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum Role {
+        /// May create proposals.
+        Proposer,
+        /// May execute queued proposals.
+        Executor,
+        /// May veto and pause like the guardian. Never open.
+        Guardian,
+        /// May manage the treasury asset registry, besides the admin. Never open.
+        Treasurer,
+    }
+
+    /// Why funds entered or left the treasury.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum Movement {
+        /// Deposit minting governance tokens, through `join` or `contribute`.
+        Deposit,
+        Donation,
+        ProposalBond,
+        /// Funds an investment returned, see `record_return`.
+        Return,
+        /// Payout approved by a proposal.
+        Payout,
+        Dividend,
+        Reward,
+        /// Bond or fundraising deposit given back.
+        Refund,
+        RageQuit,
+        /// Payout fee sent to the operations reserve.
+        Fee,
+        /// Treasury swept by an `EmergencyWithdrawal` proposal.
+        Emergency,
+    }
+
+    /// Treasury movement in the append-only ledger.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct LedgerEntry {
+        pub timestamp: u64,
+        pub movement: Movement,
+        /// Whether the funds came into the treasury.
+        pub inflow: bool,
+        pub counterparty: AccountId,
+        /// PSP22 token moved, `None` for native funds.
+        pub asset: Option<AccountId>,
+        pub amount: Balance,
+        pub proposal_id: Option<ProposalId>,
+    }
+
+    /// Native funds an account donated through `donate`.
+    #[derive(Encode, Decode, Clone, Copy, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Donation {
+        pub total: Balance,
+        pub count: u32,
+        pub last_at: Timestamp,
+    }
+
+    /// Receipt NFT minted for a deposit or donation, by its PSP34 id.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ContributionReceipt {
+        pub contributor: AccountId,
+        pub amount: Balance,
+        /// Number of `receipt_tiers` thresholds the amount reached.
+        pub tier: u8,
+    }
+
+    impl Investment {
+        /// Return on the disbursed funds in basis points, negative for a loss.
+        pub fn roi(&self) -> Option<i128> {
+            if self.disbursed == 0 {
+                return None
+            }
+            let profit = self.returned as i128 - self.disbursed as i128;
+            Some(profit * BASIS_POINTS as i128 / self.disbursed as i128)
+        }
+    }
+
+    /// Grant of `amount` vesting linearly over `duration` minutes from execution.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct StreamPayout {
+        pub to: AccountId,
+        pub amount: Balance,
+        pub duration: u64,
+    }
+
+    /// Vesting state of an executed stream. Canceling it cuts `amount` to what
+    /// had vested and moves `end` to the cancellation.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Stream {
+        pub to: AccountId,
+        pub amount: Balance,
+        pub start: u64,
+        pub end: u64,
+        pub withdrawn: Balance,
+    }
+
+    impl Stream {
+        /// Part of `amount` vested at `timestamp`.
+        pub fn vested_at(&self, timestamp: u64) -> Balance {
+            if timestamp >= self.end {
+                return self.amount
+            }
+            let elapsed = timestamp.saturating_sub(self.start);
+            self.amount * elapsed as Balance / (self.end - self.start) as Balance
+        }
+    }
+
+    /// Grant of `amount` vesting linearly over `duration` minutes from execution,
+    /// of which nothing can be claimed for the first `cliff` minutes.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct VestingGrant {
+        pub to: AccountId,
+        pub amount: Balance,
+        pub cliff: u64,
+        pub duration: u64,
+    }
+
+    /// Vesting state of an executed `Vesting` proposal.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Vesting {
+        pub to: AccountId,
+        pub amount: Balance,
+        pub start: u64,
+        pub cliff: u64,
+        pub end: u64,
+        pub claimed: Balance,
+    }
+
+    impl Vesting {
+        /// Part of `amount` vested at `timestamp`.
+        pub fn vested_at(&self, timestamp: u64) -> Balance {
+            if timestamp < self.cliff {
+                return 0
+            }
+            if timestamp >= self.end {
+                return self.amount
+            }
+            let elapsed = timestamp - self.start;
+            self.amount * elapsed as Balance / (self.end - self.start) as Balance
+        }
+    }
+
+    /// Claim progress of an executed recurring payout. The tranche of period `n`
+    /// (counting from zero) unlocks `n` periods after `start`.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct PayoutSchedule {
+        pub payout: RecurringPayout,
+        pub start: u64,
+        pub claimed_periods: u32,
+    }
+
+    /// Governor parameter and its new value. Durations are in minutes.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum GovernorParam {
+        /// In basis points of the supply at the proposal snapshot.
+        Quorum(u16),
+        VotingDelay(u64),
+        MinDuration(u64),
+        MaxDuration(u64),
+        GracePeriod(u64),
+        TimelockDelay(u64),
+        ProposalBond(Balance),
+        ProposalThreshold(Balance),
+        ProposalCooldown(u64),
+        MaxActiveProposals(u32),
+        RetentionPeriod(u64),
+        CountingMode(CountingMode),
+        ConvictionPeriod(u64),
+        MaxVoteWeight(u8),
+        ApprovalThreshold(u8),
+        VotingStrategy(Option<AccountId>),
+        WeightSources(Vec<WeightSource>),
+        RewardEpoch(u64),
+        /// In basis points, see `current_quorum`.
+        QuorumFloor(u16),
+        QuorumDecay(u64),
+        JoinRate(Balance),
+        /// Native funds proposals may commit per budget epoch. Zero disables the
+        /// budget.
+        BudgetCap(Balance),
+        BudgetEpoch(u64),
+        BudgetCarryOver(CarryOver),
+        LargePayout(Option<LargePayout>),
+        PriceOracle(Option<AccountId>),
+        CoSigning(Option<CoSigning>),
+        /// Basis points withheld from each native payout.
+        PayoutFee(u16),
+        /// Replaces or, with `None`, removes the guardian.
+        Guardian(Option<AccountId>),
+        /// Minutes after voting ends during which the guardian can veto.
+        GuardianWindow(u64),
+        GrantRole(Role, AccountId),
+        RevokeRole(Role, AccountId),
+        FeeReserve(Option<AccountId>),
+        DexRouter(Option<AccountId>),
+        StakingAdapter(Option<AccountId>),
+        ReceiptNft(Option<AccountId>),
+        /// Ascending contribution thresholds of the receipt tiers.
+        ReceiptTiers(Vec<Balance>),
+        /// Gas each call into another contract may use, zero for all that remains.
+        CallGasLimit(u64),
+        Emergency(EmergencyTerms),
+        /// Bars an account from receiving payouts.
+        Blacklist(AccountId),
+        Unblacklist(AccountId),
+        /// Restricts proposing and voting to members, see `add_member`.
+        MembersOnly(bool),
+        ComplianceRegistry(Option<AccountId>),
+        /// Replaces or, with `None`, dissolves the council.
+        Council(Option<Council>),
+        /// Adds or replaces the track with the given id.
+        Track(Track),
+        RemoveTrack(TrackId),
+        /// Minutes after voting ends during which holders can object to a passed
+        /// proposal. Zero disables objections.
+        ObjectionWindow(u64),
+        /// Objecting weight, in basis points of the total weight, that cancels a
+        /// proposal.
+        ObjectionThreshold(u16),
+        /// Pending or active proposals allowed across all proposers.
+        MaxOpenProposals(u32),
+        /// Minutes between arming the kill switch and executions freezing.
+        KillSwitchDelay(u64),
+    }
+
+    /// Forwards already encoded call arguments without a length prefix.
+    #[cfg(not(test))]
+    struct CallInput<'a>(&'a [u8]);
+
+    #[cfg(not(test))]
+    impl scale::Encode for CallInput<'_> {
+        fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    #[derive(Encode, Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Proposal {
+        pub proposer: AccountId,
+        /// What the proposal does once executed.
+        pub kind: ProposalKind,
+        pub vote_start: u64,
+        pub vote_end: u64,
+        /// Hash of the off-chain description voters are asked to approve.
+        pub description_hash: Hash,
+        pub executed: bool,
+        pub canceled: bool,
+        /// Last timestamp at which the proposal can still be executed.
+        pub expires_at: u64,
+        /// Earliest execution timestamp, set once the proposal is queued.
+        pub eta: Option<u64>,
+        /// Native deposit locked by the proposer until `settle_bond`.
+        pub bond: Balance,
+        pub bond_settled: bool,
+        /// Governor counting mode at creation, used for the whole vote.
+        pub counting_mode: CountingMode,
+        /// Governor strategy contract at creation, see `VotingStrategy`.
+        pub voting_strategy: Option<AccountId>,
+        /// Governor weight sources at creation.
+        pub weight_sources: Vec<WeightSource>,
+        /// Whether the proposal pays out above the large payout threshold, judged
+        /// at creation and on amendment.
+        pub large_payout: bool,
+        /// Track the proposal was submitted on, if any.
+        pub track: Option<Track>,
+    }
+
+    impl Proposal {
+        /// Timestamp voting power is read at. It lies strictly before `vote_start`,
+        /// so tokens moved in the block voting opens cannot be counted twice.
+        pub fn snapshot(&self) -> u64 {
+            self.vote_start.saturating_sub(1)
+        }
+    }
+
+    #[derive(Encode, Decode, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ProposalVote {
+        pub against_votes: Balance,
+        pub for_votes: Balance,
+        pub abstain_votes: Balance,
+    }
+
+    impl ProposalVote {
+        fn add(&mut self, vote: VoteType, weight: Balance) -> Result<(), GovernorError> {
+            self.apply(vote, weight, Balance::checked_add)
+        }
+
+        fn remove(
+            &mut self,
+            vote: VoteType,
+            weight: Balance,
+        ) -> Result<(), GovernorError> {
+            self.apply(vote, weight, Balance::checked_sub)
+        }
+
+        fn apply(
+            &mut self,
+            vote: VoteType,
+            weight: Balance,
+            op: fn(Balance, Balance) -> Option<Balance>,
+        ) -> Result<(), GovernorError> {
+            let overflow = GovernorError::ArithmeticOverflow;
+            match vote {
+                VoteType::Against => {
+                    self.against_votes = op(self.against_votes, weight).ok_or(overflow)?
+                }
+                VoteType::For => {
+                    self.for_votes = op(self.for_votes, weight).ok_or(overflow)?
+                }
+                VoteType::Abstain => {
+                    self.abstain_votes = op(self.abstain_votes, weight).ok_or(overflow)?
+                }
+                VoteType::Split {
+                    for_weight,
+                    against_weight,
+                } => {
+                    let for_votes = op(self.for_votes, for_weight).ok_or(overflow)?;
+                    self.against_votes =
+                        op(self.against_votes, against_weight).ok_or(overflow)?;
+                    self.for_votes = for_votes;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Extra token whose snapshot balance adds to the governance token balance,
+    /// e.g. staked LP tokens. It must support `PSP22Snapshot`.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct WeightSource {
+        pub token: AccountId,
+        /// Weight of one token, in percent of a governance token.
+        pub multiplier: u32,
+    }
+
+    /// Governance tokens escrowed by a conviction vote.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ConvictionLock {
+        pub conviction: Conviction,
+        pub amount: Balance,
+        pub unlock_at: u64,
+    }
+
+    /// Governance tokens escrowed with `lock`. Its weight decays linearly to zero
+    /// at `end`.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct VoteLock {
+        pub amount: Balance,
+        pub end: u64,
+        /// Last time the lock was created or extended. It carries no weight on
+        /// proposals whose vote started earlier.
+        pub updated_at: u64,
+    }
+
+    impl VoteLock {
+        fn weight_at(&self, timestamp: u64) -> Balance {
+            let remaining = self.end.saturating_sub(timestamp) as Balance;
+            self.amount * remaining / (MAX_VOTE_LOCK * ONE_MINUTE) as Balance
+        }
+    }
+
+    /// A ballot cast by a voter, kept so it can be changed or retracted while the
+    /// vote is open.
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Ballot {
+        pub vote: VoteType,
+        pub weight: Balance,
+        /// Delegators whose power is included in `weight`.
+        pub delegators: Vec<AccountId>,
+        /// Hash of the justification given with `vote_with_reason`.
+        pub reason_hash: Option<Hash>,
+    }
+
+    /// How an account's power was counted on a proposal. Delegators get a receipt
+    /// for the ballot their delegate cast, with their own share of its weight.
+    #[derive(Encode, Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Receipt {
+        pub vote: VoteType,
+        pub weight: Balance,
+        pub timestamp: u64,
+    }
+
+    /// Recent proposals of an account, used to rate limit proposers.
+    #[derive(Encode, Decode, Clone, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct ProposerActivity {
+        pub last_proposed_at: Option<u64>,
+        /// Proposals that were still pending or active at the last submission.
+        pub open_proposals: Vec<ProposalId>,
+    }
+
+    #[ink(event)]
+    pub struct ProposalCreated {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        proposer: AccountId,
+        kind: ProposalKind,
+        vote_start: Timestamp,
+        vote_end: Timestamp,
+        description_hash: Hash,
+    }
+
+    /// A ballot and the weight it counted with. Ballots on multi-choice proposals
+    /// are reported as `For`.
+    #[ink(event)]
+    pub struct VoteCast {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        voter: AccountId,
+        vote: VoteType,
+        weight: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ProposalExecuted {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+    }
+
+    /// A transfer made by an executed `NativeTransfer` or `TokenTransfer`
+    /// proposal. `asset` is `None` for native funds.
+    #[ink(event)]
+    pub struct PayoutSent {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        to: AccountId,
+        asset: Option<AccountId>,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct GovernorTerminated {
+        proposal_id: ProposalId,
+        distributed: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ProposalCanceled {
+        proposal_id: ProposalId,
+    }
+
+    #[ink(event)]
+    pub struct ProposalFastTracked {
+        proposal_id: ProposalId,
+        vote_end: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct ProposalVetoed {
+        proposal_id: ProposalId,
+        guardian: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ParameterChanged {
+        param: GovernorParam,
+    }
+
+    #[ink(event)]
+    pub struct MemberAdded {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MemberRemoved {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct GovernanceTokenChanged {
+        previous_token: AccountId,
+        governance_token: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AdminTransferStarted {
+        admin: AccountId,
+        pending_admin: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct AdminTransferred {
+        previous_admin: AccountId,
+        admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleGranted {
+        role: Role,
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        role: Role,
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct GovernorPaused {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct GovernorUnpaused {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct KillSwitchArmed {
+        guardian: AccountId,
+        effective_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct KillSwitchDisarmed {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ProposalPruned {
+        proposal_id: ProposalId,
+    }
+
+    /// Changes made to a pending proposal, with the values they replace.
+    #[ink(event)]
+    pub struct ProposalAmended {
+        proposal_id: ProposalId,
+        previous_kind: ProposalKind,
+        kind: ProposalKind,
+        previous_description_hash: Hash,
+        description_hash: Hash,
+    }
+
+    /// Links a ballot to the justification published off-chain.
+    #[ink(event)]
+    pub struct VoteReason {
+        proposal_id: ProposalId,
+        voter: AccountId,
+        reason_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        delegator: AccountId,
+        from_delegate: Option<AccountId>,
+        to_delegate: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct DonationReceived {
+        donor: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct Governor {
+        proposal_votes: Mapping<ProposalId, ProposalVote>,
+        proposals: Mapping<ProposalId, Proposal>,
+        receipts: Mapping<(ProposalId, AccountId), Receipt>,
+        ballots: Mapping<(ProposalId, AccountId), Ballot>,
+        choice_ballots: Mapping<(ProposalId, AccountId), ChoiceBallot>,
+        conviction_locks: Mapping<(ProposalId, AccountId), ConvictionLock>,
+        vote_locks: Mapping<AccountId, VoteLock>,
+        /// Next nonce each account must sign with in `vote_by_sig`.
+        nonces: Mapping<AccountId, u64>,
+        /// Accounts that voted on a proposal, so `prune` can clear their records.
+        voters: Mapping<ProposalId, Vec<AccountId>>,
+        payout_schedules: Mapping<ProposalId, PayoutSchedule>,
+        streams: Mapping<ProposalId, Stream>,
+        /// `Hire` proposal whose schedule in `payout_schedules` pays each
+        /// contributor.
+        payroll: Mapping<AccountId, ProposalId>,
+        milestone_grants: Mapping<ProposalId, MilestoneProgress>,
+        proposer_activity: Mapping<AccountId, ProposerActivity>,
+        /// Account each delegator has handed its voting power to.
+        delegates: Mapping<AccountId, AccountId>,
+        /// Accounts delegating to each delegate.
+        delegators: Mapping<AccountId, Vec<AccountId>>,
+        /// Native funds proposals may commit per budget epoch, zero for no limit.
+        budget_cap: Balance,
+        /// Minutes of one budget epoch.
+        budget_epoch: u64,
+        budget_carry_over: CarryOver,
+        /// Epoch `budget_remaining` was last updated in.
+        budget_period: u64,
+        budget_remaining: Balance,
+        payout_fee: u16,
+        /// Account able to veto passed proposals, see `veto`.
+        guardian: Option<AccountId>,
+        guardian_window: u64,
+        objection_window: u64,
+        objection_threshold: u16,
+        objections: Mapping<(ProposalId, AccountId), ()>,
+        /// Weight that objected to each proposal.
+        objection_weights: Mapping<ProposalId, Balance>,
+        roles: Mapping<(Role, AccountId), ()>,
+        /// Accounts proposals may not pay, see `GovernorParam::Blacklist`.
+        blacklist: Mapping<AccountId, ()>,
+        /// Whether only `members` may propose and vote.
+        members_only: bool,
+        members: Mapping<AccountId, ()>,
+        role_members: Mapping<Role, u32>,
+        /// Set while `execute` or `rage_quit` runs, refusing calls made back into
+        /// the governor from the contracts they pay or call. A `Lazy` cell is written
+        /// when set, so a callback sees it before the outer call returns.
+        /// pallet-contracts denies callbacks anyway, as the governor never allows
+        /// reentry.
+        entered: Lazy<bool>,
+        /// Blocks proposing, voting and executing, see `pause`.
+        paused: bool,
+        /// Set by an executed `Sunset` proposal.
+        terminated: bool,
+        /// When the armed kill switch freezes executions, see `arm_kill_switch`.
+        kill_switch_at: Option<Timestamp>,
+        kill_switch_delay: u64,
+        /// Operations account payout fees go to. No fee is taken without one.
+        fee_reserve: Option<AccountId>,
+        co_signing: Option<CoSigning>,
+        signatures: Mapping<(ProposalId, AccountId), ()>,
+        council: Option<Council>,
+        tracks: Mapping<TrackId, Track>,
+        /// Council members that approved fast-tracking each proposal.
+        fast_track_approvals: Mapping<ProposalId, Vec<AccountId>>,
+        /// Oracle valuing treasury assets, if any.
+        price_oracle: Option<AccountId>,
+        /// Registry accounts must be verified by to propose, vote or deposit, if
+        /// any.
+        compliance_registry: Option<AccountId>,
+        /// Gas limit of cross-contract calls, zero for no limit beyond the
+        /// caller's.
+        call_gas_limit: u64,
+        /// Requirements of proposals paying out a lot, if any.
+        large_payout: Option<LargePayout>,
+        emergency: EmergencyTerms,
+        /// Router `Swap` proposals trade on.
+        dex_router: Option<AccountId>,
+        /// Adapter `Stake` proposals stake native funds with.
+        staking_adapter: Option<AccountId>,
+        /// Native funds currently staked, without their rewards.
+        staked: Balance,
+        /// Rewards unstaking returned on top of the staked funds.
+        staking_rewards: Balance,
+        escrows: Mapping<ProposalId, Escrow>,
+        /// Native funds held in unsettled escrows, which are not part of the
+        /// treasury anymore.
+        escrowed: Balance,
+        /// Native payouts and returns of each proposal that disbursed funds.
+        investments: Mapping<ProposalId, Investment>,
+        /// Sum of all `investments`.
+        portfolio: Investment,
+        /// Companion PSP34 contract minting contribution receipts.
+        receipt_nft: Option<AccountId>,
+        receipt_tiers: Vec<Balance>,
+        contribution_receipts: Mapping<u32, ContributionReceipt>,
+        next_receipt_id: u32,
+        vestings: Mapping<ProposalId, Vesting>,
+        /// Vesting grants of each recipient not fully claimed yet.
+        vesting_grants: Mapping<AccountId, Vec<ProposalId>>,
+        rounds: Mapping<ProposalId, Round>,
+        round_contributions: Mapping<(ProposalId, AccountId), RoundContribution>,
+        /// Deposits of rounds still below their soft cap, kept for refunds.
+        raising: Balance,
+        ledger: Mapping<u64, LedgerEntry>,
+        ledger_len: u64,
+        donations: Mapping<AccountId, Donation>,
+        /// Accounts that donated, by order of their first donation.
+        donors: Mapping<u32, AccountId>,
+        donor_count: u32,
+        distributions: Mapping<DistributionId, Distribution>,
+        next_distribution_id: DistributionId,
+        dividend_claims: Mapping<(DistributionId, AccountId), ()>,
+        /// Native funds of distributions not claimed yet, which are not part of the
+        /// treasury anymore.
+        reserved_dividends: Balance,
+        /// PSP22 assets, other than the governance token, the treasury accounts for.
+        assets: Vec<AccountId>,
+        /// Proposals each account has a receipt on, cleared as they settle.
+        voted_proposals: Mapping<AccountId, Vec<ProposalId>>,
+        /// Proposals each account voted on, by epoch.
+        participation: Mapping<(u64, AccountId), u32>,
+        /// Sum of `participation` over all accounts, by epoch.
+        epoch_participation: Mapping<u64, u32>,
+        /// Participation rewards funded for each epoch.
+        reward_pools: Mapping<u64, Balance>,
+        /// Native funds of reward pools not claimed yet, which are not part of the
+        /// treasury anymore.
+        reserved_rewards: Balance,
+        /// Native funds of executed streams and vesting grants not paid out yet,
+        /// which are not part of the treasury anymore.
+        reserved_grants: Balance,
+        /// Proposal bonds not settled yet, which are not part of the treasury
+        /// until `settle_bond` keeps them.
+        reserved_bonds: Balance,
+        next_proposal_id: ProposalId,
+        config: GovernanceConfig,
+        governance_token: AccountId,
+        admin: AccountId,
+        /// Account that becomes admin once it calls `accept_admin`.
+        pending_admin: Option<AccountId>,
+        /// Set by an executed upgrade until `migrate` has run on the new code.
+        migration_pending: bool,
+        /// Layout version the storage was last migrated to.
+        version: u32,
+        /// Native deposit required to submit a proposal.
+        proposal_bond: Balance,
+        /// Minutes an account must wait between two proposals.
+        proposal_cooldown: u64,
+        /// Pending or active proposals allowed per proposer, zero for no limit.
+        max_active_proposals: u32,
+        /// Pending or active proposals allowed in total, zero for no limit.
+        max_open_proposals: u32,
+        /// Proposals not settled yet, i.e. pending, active, succeeded or queued,
+        /// when the last proposal was submitted.
+        open_proposals: Vec<ProposalId>,
+        /// Minutes a finished proposal is kept after its grace period before it can
+        /// be pruned.
+        retention_period: u64,
+        /// Counting mode given to new proposals.
+        counting_mode: CountingMode,
+        /// Strategy contract given to new proposals. It replaces the counting mode.
+        voting_strategy: Option<AccountId>,
+        /// Tokens summed with the governance token under linear and quadratic
+        /// counting.
+        weight_sources: Vec<WeightSource>,
+        /// Minutes of one conviction lock period.
+        conviction_period: u64,
+        /// Governance tokens held in conviction and vote-escrow locks, which
+        /// proposals cannot spend.
+        locked_tokens: Balance,
+        /// Largest share, in percent of the supply, a single account adds to a
+        /// tally. Zero disables the cap.
+        max_vote_weight: u8,
+        /// Minutes of one participation epoch. A vote counts towards the epoch its
+        /// proposal's voting started in. Zero disables participation tracking.
+        reward_epoch: u64,
+        /// Governance tokens minted per unit of native currency deposited with
+        /// `join`. Zero disables joining.
+        join_rate: Balance,
+    }
+
+    impl Governor {
+        #[ink(constructor, payable)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn new(
+            governance_token: AccountId,
+            quorum: u16,
+            voting_delay: u64,
+            min_duration: u64,
+            max_duration: u64,
+            grace_period: u64,
+            timelock_delay: u64,
+            max_vote_weight: u8,
+            guardian: Option<AccountId>,
+        ) -> Self {
+            Self {
+                proposal_votes: Mapping::default(),
+                proposals: Mapping::default(),
+                receipts: Mapping::default(),
+                ballots: Mapping::default(),
+                choice_ballots: Mapping::default(),
+                conviction_locks: Mapping::default(),
+                vote_locks: Mapping::default(),
+                nonces: Mapping::default(),
+                voters: Mapping::default(),
+                payout_schedules: Mapping::default(),
+                streams: Mapping::default(),
+                payroll: Mapping::default(),
+                milestone_grants: Mapping::default(),
+                proposer_activity: Mapping::default(),
+                delegates: Mapping::default(),
+                delegators: Mapping::default(),
+                budget_cap: 0,
+                budget_epoch: 30 * 24 * 60,
+                budget_carry_over: CarryOver::None,
+                budget_period: 0,
+                budget_remaining: 0,
+                payout_fee: 0,
+                guardian,
+                guardian_window: 7 * 24 * 60,
+                objection_window: 0,
+                objection_threshold: 1_000,
+                objections: Mapping::default(),
+                objection_weights: Mapping::default(),
+                roles: Mapping::default(),
+                blacklist: Mapping::default(),
+                members_only: false,
+                members: Mapping::default(),
+                role_members: Mapping::default(),
+                entered: Lazy::default(),
+                paused: false,
+                terminated: false,
+                kill_switch_at: None,
+                kill_switch_delay: 2 * 24 * 60,
+                fee_reserve: None,
+                co_signing: None,
+                signatures: Mapping::default(),
+                council: None,
+                tracks: Mapping::default(),
+                fast_track_approvals: Mapping::default(),
+                price_oracle: None,
+                compliance_registry: None,
+                call_gas_limit: 0,
+                large_payout: None,
+                emergency: EmergencyTerms {
+                    quorum: 6_667,
+                    approval_threshold: 75,
+                    timelock_delay: 0,
+                },
+                dex_router: None,
+                staking_adapter: None,
+                staked: 0,
+                staking_rewards: 0,
+                escrows: Mapping::default(),
+                escrowed: 0,
+                investments: Mapping::default(),
+                portfolio: Investment::default(),
+                receipt_nft: None,
+                receipt_tiers: Vec::new(),
+                contribution_receipts: Mapping::default(),
+                next_receipt_id: 0,
+                vestings: Mapping::default(),
+                vesting_grants: Mapping::default(),
+                rounds: Mapping::default(),
+                round_contributions: Mapping::default(),
+                raising: 0,
+                ledger: Mapping::default(),
+                ledger_len: 0,
+                donations: Mapping::default(),
+                donors: Mapping::default(),
+                donor_count: 0,
+                distributions: Mapping::default(),
+                next_distribution_id: 0,
+                dividend_claims: Mapping::default(),
+                reserved_dividends: 0,
+                assets: Vec::new(),
+                voted_proposals: Mapping::default(),
+                participation: Mapping::default(),
+                epoch_participation: Mapping::default(),
+                reward_pools: Mapping::default(),
+                reserved_rewards: 0,
+                reserved_grants: 0,
+                reserved_bonds: 0,
+                next_proposal_id: 0,
+                config: GovernanceConfig {
+                    quorum,
+                    quorum_floor: quorum,
+                    quorum_decay: 0,
+                    proposal_threshold: 0,
+                    approval_threshold: 50,
+                    voting_delay,
+                    min_duration,
+                    max_duration,
+                    grace_period,
+                    timelock_delay,
+                },
+                governance_token,
+                admin: Self::env().caller(),
+                pending_admin: None,
+                migration_pending: false,
+                version: STORAGE_VERSION,
+                proposal_bond: 0,
+                proposal_cooldown: 0,
+                max_active_proposals: 0,
+                max_open_proposals: 0,
+                open_proposals: Vec::new(),
+                retention_period: 0,
+                counting_mode: CountingMode::Linear,
+                voting_strategy: None,
+                weight_sources: Vec::new(),
+                conviction_period: 0,
+                locked_tokens: 0,
+                max_vote_weight,
+                reward_epoch: 0,
+                join_rate: 0,
+            }
+        }
+
+        #[ink(message, payable)]
+        pub fn propose(
+            &mut self,
+            to: AccountId,
+            amount: Balance,
+            duration: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.create_proposal(
+                ProposalKind::NativeTransfer(vec![Transfer { to, amount }]),
+                duration,
+                self.config.voting_delay,
+                description_hash,
+            )
+        }
+
+        /// Same as `propose`, but opens voting `voting_delay` minutes from now
+        /// instead of after the governor's default delay.
+        #[ink(message, payable)]
+        pub fn propose_with_delay(
+            &mut self,
+            to: AccountId,
+            amount: Balance,
+            duration: u64,
+            voting_delay: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.create_proposal(
+                ProposalKind::NativeTransfer(vec![Transfer { to, amount }]),
+                duration,
+                voting_delay,
+                description_hash,
+            )
+        }
+
+        /// Submits a proposal of any kind.
+        #[ink(message, payable)]
+        pub fn propose_kind(
+            &mut self,
+            kind: ProposalKind,
+            duration: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.create_proposal(
+                kind,
+                duration,
+                self.config.voting_delay,
+                description_hash,
+            )
+        }
+
+        /// Submits a proposal on a referendum track, open for votes for the track's
+        /// voting period and decided under its requirements.
+        #[ink(message, payable)]
+        pub fn propose_on_track(
+            &mut self,
+            track_id: TrackId,
+            kind: ProposalKind,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            let track = self
+                .tracks
+                .get(track_id)
+                .ok_or(GovernorError::TrackNotFound)?;
+            self.create_proposal_on(
+                kind,
+                track.voting_period,
+                self.config.voting_delay,
+                description_hash,
+                Some(track),
+            )
+        }
+
+        #[ink(message)]
+        pub fn get_track(&self, track_id: TrackId) -> Option<Track> {
+            self.tracks.get(track_id)
+        }
+
+        /// Proposes several payouts at once, e.g. every contributor of a funding
+        /// round, to be executed atomically.
+        #[ink(message, payable)]
+        pub fn propose_batch(
+            &mut self,
+            transfers: Vec<Transfer>,
+            duration: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.propose_kind(
+                ProposalKind::NativeTransfer(transfers),
+                duration,
+                description_hash,
+            )
+        }
+
+        /// Proposes paying out `amount` of the PSP22 `asset` held by the governor.
+        #[ink(message, payable)]
+        pub fn propose_token_transfer(
+            &mut self,
+            asset: AccountId,
+            to: AccountId,
+            amount: Balance,
+            duration: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.propose_kind(
+                ProposalKind::TokenTransfer(vec![TokenTransfer { asset, to, amount }]),
+                duration,
+                description_hash,
+            )
+        }
+
+        /// Proposes a call into another contract (e.g. the governance token) to be
+        /// made by the governor, letting the DAO govern contracts it has rights on.
+        /// Calls aimed at the governor itself reach the messages only it may call,
+        /// such as `update_config`.
+        #[ink(message, payable)]
+        pub fn propose_call(
+            &mut self,
+            call: ContractCall,
+            duration: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.propose_kind(ProposalKind::GenericCall(call), duration, description_hash)
+        }
+
+        fn create_proposal(
+            &mut self,
+            kind: ProposalKind,
+            duration: u64,
+            voting_delay: u64,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.validate_duration(duration)?;
+            self.create_proposal_on(kind, duration, voting_delay, description_hash, None)
+        }
+
+        fn create_proposal_on(
+            &mut self,
+            kind: ProposalKind,
+            duration: u64,
+            voting_delay: u64,
+            description_hash: Hash,
+            track: Option<Track>,
+        ) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            self.ensure_not_paused()?;
+            if self.terminated {
+                return Err(GovernorError::Terminated)
+            }
+            Self::validate_kind(&kind)?;
+            self.ensure_recipients_allowed(&kind)?;
+            let proposer = self.env().caller();
+            self.ensure_role(Role::Proposer, proposer)?;
+            self.ensure_member(proposer)?;
+            self.ensure_verified(proposer)?;
+            if self.config.proposal_threshold > 0
+                && self.balance_of(proposer)? < self.config.proposal_threshold
+            {
+                return Err(GovernorError::BelowProposalThreshold)
+            }
+            let bond = self.env().transferred_value();
+            if bond != self.proposal_bond {
+                return Err(GovernorError::IncorrectBond)
+            }
+            let mut activity = self.check_proposer_activity(proposer)?;
+            let mut open_proposals = self.check_open_proposals()?;
+            let vote_start = Self::minutes_after(self.now(), voting_delay)?;
+            let vote_end = Self::minutes_after(vote_start, duration)?;
+            let expires_at = Self::minutes_after(vote_end, self.config.grace_period)?;
+            let proposal_id = self.next_proposal_id;
+            let next_proposal_id = proposal_id
+                .checked_add(1)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            let reserved_bonds = self
+                .reserved_bonds
+                .checked_add(bond)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            if bond > 0 {
+                self.log_movement(
+                    Movement::ProposalBond,
+                    true,
+                    proposer,
+                    None,
+                    bond,
+                    Some(proposal_id),
+                );
+            }
+
+            let proposal = Proposal {
+                proposer,
+                kind,
+                vote_start,
+                vote_end,
+                description_hash,
+                executed: false,
+                canceled: false,
+                expires_at,
+                eta: None,
+                bond,
+                bond_settled: false,
+                counting_mode: self.counting_mode,
+                voting_strategy: self.voting_strategy,
+                weight_sources: self.weight_sources.clone(),
+                large_payout: false,
+                track,
+            };
+            self.ensure_within_track(proposal_id, &proposal.kind, track)?;
+
+            let proposal = Proposal {
+                large_payout: self.is_large_payout(proposal_id, &proposal.kind),
+                ..proposal
+            };
+            self.next_proposal_id = next_proposal_id;
+            self.reserved_bonds = reserved_bonds;
+            self.proposals.insert(proposal_id, &proposal);
+            self.env().emit_event(ProposalCreated {
+                proposal_id,
+                proposer,
+                kind: proposal.kind,
+                vote_start,
+                vote_end,
+                description_hash,
+            });
+            activity.last_proposed_at = Some(self.now());
+            activity.open_proposals.push(proposal_id);
+            self.proposer_activity.insert(proposer, &activity);
+            open_proposals.push(proposal_id);
+            self.open_proposals = open_proposals;
+
+            Ok(())
+        }
+
+        /// Lets the proposer replace the content and description of a proposal while
+        /// it is still pending, so a mistake does not cost the proposal id.
+        #[ink(message)]
+        pub fn amend_proposal(
+            &mut self,
+            proposal_id: ProposalId,
+            kind: ProposalKind,
+            description_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            let mut proposal = self.amendable_proposal(proposal_id)?;
+            Self::validate_kind(&kind)?;
+            self.ensure_recipients_allowed(&kind)?;
+            self.ensure_within_track(proposal_id, &kind, proposal.track)?;
+
+            proposal.large_payout = self.is_large_payout(proposal_id, &kind);
+            let previous_kind = core::mem::replace(&mut proposal.kind, kind.clone());
+            let previous_description_hash =
+                core::mem::replace(&mut proposal.description_hash, description_hash);
+            self.proposals.insert(proposal_id, &proposal);
+            self.env().emit_event(ProposalAmended {
+                proposal_id,
+                previous_kind,
+                kind,
+                previous_description_hash,
+                description_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Lets the proposer correct the recipient or amount of a native transfer
+        /// proposal whose voting period has not opened yet. The proposal's payouts
+        /// are replaced by a single transfer.
+        #[ink(message)]
+        pub fn edit_proposal(
+            &mut self,
+            proposal_id: ProposalId,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            let proposal = self.amendable_proposal(proposal_id)?;
+            if !matches!(proposal.kind, ProposalKind::NativeTransfer(_)) {
+                return Err(GovernorError::InvalidProposalKind)
+            }
+            self.amend_proposal(
+                proposal_id,
+                ProposalKind::NativeTransfer(vec![Transfer { to, amount }]),
+                proposal.description_hash,
+            )
+        }
+
+        /// Withdraws a proposal that has not been executed yet. Only the proposer or
+        /// the governor admin may cancel.
+        #[ink(message)]
+        pub fn cancel(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            let caller = self.env().caller();
+            if caller != proposal.proposer && caller != self.admin {
+                return Err(GovernorError::OnlyProposer)
+            }
+            if proposal.executed {
+                return Err(GovernorError::ProposalAlreadyExecuted)
+            }
+            if proposal.canceled {
+                return Err(GovernorError::ProposalCanceled)
+            }
+
+            proposal.canceled = true;
+            self.proposals.insert(proposal_id, &proposal);
+            self.env().emit_event(ProposalCanceled { proposal_id });
+
+            Ok(())
+        }
+
+        /// Cancels a proposal that passed but was not executed yet, within
+        /// `guardian_window` minutes of the end of its vote. Guardian only.
+        #[ink(message)]
+        pub fn veto(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let guardian = self.env().caller();
+            self.ensure_guardian(guardian)?;
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            self.ensure_succeeded(proposal_id, &proposal)?;
+            if self.now() > Self::minutes_after(proposal.vote_end, self.guardian_window)?
+            {
+                return Err(GovernorError::VetoWindowClosed)
+            }
+
+            proposal.canceled = true;
+            self.proposals.insert(proposal_id, &proposal);
+            self.env().emit_event(ProposalVetoed {
+                proposal_id,
+                guardian,
+            });
+
+            Ok(())
+        }
+
+        /// Whether a `Sunset` proposal dissolved the DAO.
+        #[ink(message)]
+        pub fn terminated(&self) -> bool {
+            self.terminated
+        }
+
+        #[ink(message)]
+        pub fn guardian(&self) -> Option<AccountId> {
+            self.guardian
+        }
+
+        /// Objects to a passed proposal with the caller's voting power, within
+        /// `objection_window` minutes of the end of its vote. Once the objecting
+        /// weight exceeds `objection_threshold` the proposal is canceled.
+        #[ink(message)]
+        pub fn object(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            self.ensure_succeeded(proposal_id, &proposal)?;
+            if !self.objection_window_open(&proposal) {
+                return Err(GovernorError::ObjectionWindowClosed)
+            }
+            let caller = self.env().caller();
+            if self.objections.contains((proposal_id, caller)) {
+                return Err(GovernorError::AlreadyObjected)
+            }
+            let weight = self.voting_power(caller, &proposal)?;
+            let objection_weight = self
+                .objection_weights
+                .get(proposal_id)
+                .unwrap_or_default()
+                .checked_add(weight)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            self.objections.insert((proposal_id, caller), &());
+            self.objection_weights
+                .insert(proposal_id, &objection_weight);
+
+            let total_weight = self.total_weight(&proposal)?;
+            if objection_weight.saturating_mul(BASIS_POINTS as Balance)
+                > total_weight.saturating_mul(self.objection_threshold as Balance)
+            {
+                proposal.canceled = true;
+                self.proposals.insert(proposal_id, &proposal);
+                self.env().emit_event(ProposalCanceled { proposal_id });
+            }
+            Ok(())
+        }
+
+        /// Weight that objected to a proposal so far.
+        #[ink(message)]
+        pub fn objections(&self, proposal_id: ProposalId) -> Balance {
+            self.objection_weights.get(proposal_id).unwrap_or_default()
+        }
+
+        fn objection_window_open(&self, proposal: &Proposal) -> bool {
+            self.objection_window > 0
+                && match Self::minutes_after(proposal.vote_end, self.objection_window) {
+                    Ok(closes_at) => self.now() <= closes_at,
+                    Err(_) => true,
+                }
+        }
+
+        /// Blocks proposing, voting and executing until `unpause`. Guardian or admin
+        /// only.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), GovernorError> {
+            let account = self.env().caller();
+            self.ensure_guardian_or_admin(account)?;
+            self.paused = true;
+            self.env().emit_event(GovernorPaused { account });
+            Ok(())
+        }
+
+        /// Guardian or admin only.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), GovernorError> {
+            let account = self.env().caller();
+            self.ensure_guardian_or_admin(account)?;
+            self.paused = false;
+            self.env().emit_event(GovernorUnpaused { account });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Freezes executions `kill_switch_delay` minutes from now, unless
+        /// governance disarms it first. Guardian only.
+        #[ink(message)]
+        pub fn arm_kill_switch(&mut self) -> Result<(), GovernorError> {
+            let guardian = self.env().caller();
+            self.ensure_guardian(guardian)?;
+            let effective_at = Self::minutes_after(self.now(), self.kill_switch_delay)?;
+            self.kill_switch_at = Some(effective_at);
+            self.env().emit_event(KillSwitchArmed {
+                guardian,
+                effective_at,
+            });
+            Ok(())
+        }
+
+        /// Governance or admin only.
+        #[ink(message)]
+        pub fn disarm_kill_switch(&mut self) -> Result<(), GovernorError> {
+            let account = self.env().caller();
+            self.ensure_self_or_admin(account)?;
+            self.disarm(account)
+        }
+
+        fn disarm(&mut self, account: AccountId) -> Result<(), GovernorError> {
+            if self.kill_switch_at.take().is_none() {
+                return Err(GovernorError::KillSwitchNotArmed)
+            }
+            self.env().emit_event(KillSwitchDisarmed { account });
+            Ok(())
+        }
+
+        /// When the armed kill switch freezes executions.
+        #[ink(message)]
+        pub fn kill_switch_at(&self) -> Option<Timestamp> {
+            self.kill_switch_at
+        }
+
+        fn ensure_kill_switch_off(&self) -> Result<(), GovernorError> {
+            if matches!(self.kill_switch_at, Some(at) if self.now() >= at) {
+                return Err(GovernorError::KillSwitchEngaged)
+            }
+            Ok(())
+        }
+
+        fn ensure_guardian_or_admin(
+            &self,
+            account: AccountId,
+        ) -> Result<(), GovernorError> {
+            if account != self.admin {
+                self.ensure_guardian(account)?;
+            }
+            Ok(())
+        }
+
+        fn ensure_guardian(&self, account: AccountId) -> Result<(), GovernorError> {
+            if !self.is_guardian(account) {
+                return Err(GovernorError::Unauthorized)
+            }
+            Ok(())
+        }
+
+        fn ensure_admin(&self) -> Result<(), GovernorError> {
+            if self.env().caller() != self.admin {
+                return Err(GovernorError::Unauthorized)
+            }
+            Ok(())
+        }
+
+        fn is_guardian(&self, account: AccountId) -> bool {
+            self.guardian == Some(account) || self.has_role(Role::Guardian, account)
+        }
+
+        /// Offers the admin role to `pending_admin`, who must accept it with
+        /// `accept_admin`. `None` withdraws the offer. Admin only.
+        #[ink(message)]
+        pub fn transfer_admin(
+            &mut self,
+            pending_admin: Option<AccountId>,
+        ) -> Result<(), GovernorError> {
+            self.ensure_admin()?;
+            self.pending_admin = pending_admin;
+            self.env().emit_event(AdminTransferStarted {
+                admin: self.admin,
+                pending_admin,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn accept_admin(&mut self) -> Result<(), GovernorError> {
+            let caller = self.env().caller();
+            if self.pending_admin != Some(caller) {
+                return Err(GovernorError::NotPendingAdmin)
+            }
+            let previous_admin = core::mem::replace(&mut self.admin, caller);
+            self.pending_admin = None;
+            self.env().emit_event(AdminTransferred {
+                previous_admin,
+                admin: caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        #[ink(message)]
+        pub fn pending_admin(&self) -> Option<AccountId> {
+            self.pending_admin
+        }
+
+        /// Gives `account` a role. Admin only; governance can do the same with
+        /// `GovernorParam::GrantRole`.
+        #[ink(message)]
+        pub fn grant_role(
+            &mut self,
+            role: Role,
+            account: AccountId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_admin()?;
+            self.add_role(role, account);
+            Ok(())
+        }
+
+        /// Admin only.
+        #[ink(message)]
+        pub fn revoke_role(
+            &mut self,
+            role: Role,
+            account: AccountId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_admin()?;
+            self.remove_role(role, account);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn renounce_role(&mut self, role: Role) -> Result<(), GovernorError> {
+            let account = self.env().caller();
+            if !self.has_role(role, account) {
+                return Err(GovernorError::MissingRole)
+            }
+            self.remove_role(role, account);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.contains((role, account))
+        }
+
+        #[ink(message)]
+        pub fn is_blacklisted(&self, account: AccountId) -> bool {
+            self.blacklist.contains(account)
+        }
+
+        /// Registers a member. Only callable by the governor itself, from a
+        /// `GenericCall` proposal.
+        #[ink(message)]
+        pub fn add_member(&mut self, account: AccountId) -> Result<(), GovernorError> {
+            self.ensure_self()?;
+            self.admit_member(account);
+            Ok(())
+        }
+
+        fn admit_member(&mut self, account: AccountId) {
+            if self.members.insert(account, &()).is_none() {
+                self.env().emit_event(MemberAdded { account });
+            }
+        }
+
+        /// Removes a member. Only callable by the governor itself, from a
+        /// `GenericCall` proposal.
+        #[ink(message)]
+        pub fn remove_member(&mut self, account: AccountId) -> Result<(), GovernorError> {
+            self.ensure_self()?;
+            self.expel_member(account);
+            Ok(())
+        }
+
+        fn expel_member(&mut self, account: AccountId) {
+            if self.members.take(account).is_some() {
+                self.env().emit_event(MemberRemoved { account });
+            }
+        }
+
+        #[ink(message)]
+        pub fn is_member(&self, account: AccountId) -> bool {
+            self.members.contains(account)
+        }
+
+        #[ink(message)]
+        pub fn members_only(&self) -> bool {
+            self.members_only
+        }
+
+        #[ink(message)]
+        pub fn compliance_registry(&self) -> Option<AccountId> {
+            self.compliance_registry
+        }
+
+        fn ensure_verified(&self, account: AccountId) -> Result<(), GovernorError> {
+            match self.compliance_registry {
+                Some(registry) if !self.is_verified(registry, account)? => {
+                    Err(GovernorError::NotVerified)
+                }
+                _ => Ok(()),
+            }
+        }
+
+        fn ensure_member(&self, account: AccountId) -> Result<(), GovernorError> {
+            if self.members_only && !self.members.contains(account) {
+                return Err(GovernorError::NotMember)
+            }
+            Ok(())
+        }
+
+        fn add_role(&mut self, role: Role, account: AccountId) {
+            if self.has_role(role, account) {
+                return
+            }
+            self.roles.insert((role, account), &());
+            let members = self.role_members.get(role).unwrap_or(0);
+            self.role_members.insert(role, &(members + 1));
+            self.env().emit_event(RoleGranted { role, account });
+        }
+
+        fn remove_role(&mut self, role: Role, account: AccountId) {
+            if !self.has_role(role, account) {
+                return
+            }
+            self.roles.remove((role, account));
+            let members = self.role_members.get(role).unwrap_or(0);
+            self.role_members.insert(role, &(members - 1));
+            self.env().emit_event(RoleRevoked { role, account });
+        }
+
+        /// Open roles let anyone through until someone is granted them.
+        fn ensure_role(
+            &self,
+            role: Role,
+            account: AccountId,
+        ) -> Result<(), GovernorError> {
+            let open = matches!(role, Role::Proposer | Role::Executor)
+                && self.role_members.get(role).unwrap_or(0) == 0;
+            if !open && !self.has_role(role, account) {
+                return Err(GovernorError::MissingRole)
+            }
+            Ok(())
+        }
+
+        /// Casts the caller's ballot. Calling it again while the vote is open replaces
+        /// the previous ballot.
+        #[ink(message)]
+        pub fn vote(
+            &mut self,
+            proposal_id: ProposalId,
+            vote: VoteType,
+        ) -> Result<(), GovernorError> {
+            self.cast_vote(self.env().caller(), proposal_id, vote, None)
+        }
+
+        /// Splits the caller's weight between For and Against.
+        #[ink(message)]
+        pub fn vote_split(
+            &mut self,
+            proposal_id: ProposalId,
+            for_weight: Balance,
+            against_weight: Balance,
+        ) -> Result<(), GovernorError> {
+            self.cast_vote(
+                self.env().caller(),
+                proposal_id,
+                VoteType::Split {
+                    for_weight,
+                    against_weight,
+                },
+                None,
+            )
+        }
+
+        /// Same as `vote`, but records the hash of a short justification with the
+        /// ballot, e.g. of a governance forum post.
+        #[ink(message)]
+        pub fn vote_with_reason(
+            &mut self,
+            proposal_id: ProposalId,
+            vote: VoteType,
+            reason_hash: Hash,
+        ) -> Result<(), GovernorError> {
+            self.cast_vote(self.env().caller(), proposal_id, vote, Some(reason_hash))?;
+            self.env().emit_event(VoteReason {
+                proposal_id,
+                voter: self.env().caller(),
+                reason_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Casts a ballot signed off-chain by `voter`, so anyone can relay it and pay
+        /// the fee. `signature` is an ECDSA signature over `ballot_hash`, and the
+        /// voter's account must be derived from the signing key.
+        #[ink(message)]
+        pub fn vote_by_sig(
+            &mut self,
+            proposal_id: ProposalId,
+            vote: VoteType,
+            voter: AccountId,
+            signature: [u8; 65],
+            nonce: u64,
+        ) -> Result<(), GovernorError> {
+            if nonce != self.get_nonce(voter) {
+                return Err(GovernorError::InvalidNonce)
+            }
+            let message_hash = self.ballot_hash(proposal_id, vote, voter, nonce);
+            let mut public_key = [0; 33];
+            ink::env::ecdsa_recover(&signature, &message_hash, &mut public_key)
+                .map_err(|_| GovernorError::InvalidSignature)?;
+            let mut signer = [0; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public_key, &mut signer);
+            if AccountId::from(signer) != voter {
+                return Err(GovernorError::InvalidSignature)
+            }
+
+            self.cast_vote(voter, proposal_id, vote, None)?;
+            self.nonces.insert(voter, &(nonce + 1));
+
+            Ok(())
+        }
+
+        /// Message a voter signs for `vote_by_sig`. It is bound to this governor so a
+        /// signature cannot be replayed on another deployment.
+        #[ink(message)]
+        pub fn ballot_hash(
+            &self,
+            proposal_id: ProposalId,
+            vote: VoteType,
+            voter: AccountId,
+            nonce: u64,
+        ) -> [u8; 32] {
+            let mut message_hash = [0; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(self.env().account_id(), proposal_id, vote, voter, nonce),
+                &mut message_hash,
+            );
+            message_hash
+        }
+
+        #[ink(message)]
+        pub fn get_nonce(&self, account: AccountId) -> u64 {
+            self.nonces.get(account).unwrap_or_default()
+        }
+
+        /// Votes on several proposals at once. Each ballot is cast independently, so
+        /// a failing one does not undo the others; its error is returned in place.
+        #[ink(message)]
+        pub fn vote_batch(
+            &mut self,
+            ballots: Vec<(ProposalId, VoteType)>,
+        ) -> Vec<Result<(), GovernorError>> {
+            ballots
+                .into_iter()
+                .map(|(proposal_id, vote)| {
+                    self.cast_vote(self.env().caller(), proposal_id, vote, None)
+                })
+                .collect()
+        }
+
+        /// Validates the ballot before touching storage, so a failing ballot in
+        /// `vote_batch` leaves no partial state behind.
+        fn cast_vote(
+            &mut self,
+            voter: AccountId,
+            proposal_id: ProposalId,
+            vote: VoteType,
+            reason_hash: Option<Hash>,
+        ) -> Result<(), GovernorError> {
+            let proposal = self.open_proposal(proposal_id)?;
+            self.ensure_member(voter)?;
+            self.ensure_verified(voter)?;
+            if matches!(proposal.kind, ProposalKind::MultiChoice(_)) {
+                return Err(GovernorError::InvalidProposalKind)
+            }
+            let previous_ballot = self.ballots.get((proposal_id, voter));
+            if previous_ballot.is_none() && self.receipts.contains((proposal_id, voter)) {
+                return Err(GovernorError::AlreadyVoted)
+            }
+            if self.delegates.contains(voter) {
+                return Err(GovernorError::VotingPowerDelegated)
+            }
+            let (own_weight, delegated) = self.ballot_weight(
+                voter,
+                &proposal,
+                proposal_id,
+                previous_ballot.as_ref(),
+            )?;
+            let weight = delegated
+                .iter()
+                .try_fold(own_weight, |total, (_, weight)| total.checked_add(*weight))
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            if let VoteType::Split {
+                for_weight,
+                against_weight,
+            } = vote
+            {
+                if for_weight.saturating_add(against_weight) > weight {
+                    return Err(GovernorError::SplitExceedsWeight)
+                }
+            }
+
+            let mut proposal_vote =
+                self.proposal_votes.get(proposal_id).unwrap_or_default();
+            match previous_ballot {
+                Some(ballot) => {
+                    self.remove_ballot(proposal_id, voter, ballot, &mut proposal_vote)?
+                }
+                None => self.record_participation(&proposal, voter, true),
+            }
+            self.record_receipts(proposal_id, vote, voter, own_weight, &delegated);
+
+            proposal_vote.add(vote, weight)?;
+            self.proposal_votes.insert(proposal_id, &proposal_vote);
+            let ballot = Ballot {
+                vote,
+                weight,
+                delegators: delegated.into_iter().map(|(account, _)| account).collect(),
+                reason_hash,
+            };
+            self.ballots.insert((proposal_id, voter), &ballot);
+            self.env().emit_event(VoteCast {
+                proposal_id,
+                voter,
+                vote,
+                weight,
+            });
+
+            Ok(())
+        }
+
+        /// Ranks the options of a multi-choice proposal, most preferred first. Under
+        /// plurality only the first entry counts. The ballot's weight also counts as
+        /// For, so quorum and approval apply as for any proposal.
+        #[ink(message)]
+        pub fn vote_choice(
+            &mut self,
+            proposal_id: ProposalId,
+            ranking: Vec<u8>,
+        ) -> Result<(), GovernorError> {
+            let proposal = self.open_proposal(proposal_id)?;
+            self.ensure_member(self.env().caller())?;
+            self.ensure_verified(self.env().caller())?;
+            let ProposalKind::MultiChoice(choice) = &proposal.kind else {
+                return Err(GovernorError::InvalidProposalKind)
+            };
+            let mut seen = [false; MAX_CHOICES];
+            for option in ranking.iter().map(|option| *option as usize) {
+                if option >= choice.options.len() || seen[option] {
+                    return Err(GovernorError::InvalidChoice)
+                }
+                seen[option] = true;
+            }
+            if ranking.is_empty() {
+                return Err(GovernorError::InvalidChoice)
+            }
+            let voter = self.env().caller();
+            if self.receipts.contains((proposal_id, voter)) {
+                return Err(GovernorError::AlreadyVoted)
+            }
+            if self.delegates.contains(voter) {
+                return Err(GovernorError::VotingPowerDelegated)
+            }
+
+            let (own_weight, delegated) =
+                self.ballot_weight(voter, &proposal, proposal_id, None)?;
+            let weight = delegated
+                .iter()
+                .try_fold(own_weight, |total, (_, weight)| total.checked_add(*weight))
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            self.record_receipts(
+                proposal_id,
+                VoteType::For,
+                voter,
+                own_weight,
+                &delegated,
+            );
+            self.record_participation(&proposal, voter, true);
+            let mut proposal_vote =
+                self.proposal_votes.get(proposal_id).unwrap_or_default();
+            proposal_vote.add(VoteType::For, weight)?;
+            self.proposal_votes.insert(proposal_id, &proposal_vote);
+            self.choice_ballots
+                .insert((proposal_id, voter), &ChoiceBallot { ranking, weight });
+            self.env().emit_event(VoteCast {
+                proposal_id,
+                voter,
+                vote: VoteType::For,
+                weight,
+            });
+
+            Ok(())
+        }
+
+        /// Index of the option currently winning a multi-choice proposal, if any
+        /// ballot was cast.
+        #[ink(message)]
+        pub fn choice_winner(&self, proposal_id: ProposalId) -> Option<u8> {
+            let proposal = self.proposals.get(proposal_id)?;
+            let ProposalKind::MultiChoice(choice) = &proposal.kind else {
+                return None
+            };
+            let ballots: Vec<ChoiceBallot> = self
+                .voters
+                .get(proposal_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|voter| self.choice_ballots.get((proposal_id, voter)))
+                .collect();
+
+            let mut eliminated = vec![false; choice.options.len()];
+            loop {
+                let mut tallies = vec![0; choice.options.len()];
+                for ballot in &ballots {
+                    if let Some(option) = ballot
+                        .ranking
+                        .iter()
+                        .find(|option| !eliminated[**option as usize])
+                    {
+                        tallies[*option as usize] += ballot.weight;
+                    }
+                }
+                let total: Balance = tallies.iter().sum();
+                if total == 0 {
+                    return None
+                }
+                // Ties go to the option listed first.
+                let (leader, leader_votes) = tallies
+                    .iter()
+                    .enumerate()
+                    .filter(|(option, _)| !eliminated[*option])
+                    .fold((0, 0), |best, (option, votes)| {
+                        if *votes > best.1 {
+                            (option, *votes)
+                        } else {
+                            best
+                        }
+                    });
+                if matches!(choice.rule, WinnerRule::Plurality)
+                    || leader_votes * 2 > total
+                {
+                    return Some(leader as u8)
+                }
+                let weakest = (0..tallies.len())
+                    .rev()
+                    .filter(|option| !eliminated[*option])
+                    .min_by_key(|option| tallies[*option])?;
+                eliminated[weakest] = true;
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_choice_ballot(
+            &self,
+            proposal_id: ProposalId,
+            account: AccountId,
+        ) -> Option<ChoiceBallot> {
+            self.choice_ballots.get((proposal_id, account))
+        }
+
+        /// Weight `voter` casts on `proposal` itself, and the delegators counted with
+        /// it along with the weight each contributes.
+        ///
+        /// Walks the accounts delegating to the voter, directly or through a chain of
+        /// up to `MAX_DELEGATION_HOPS`; longer chains formed by later delegations
+        /// upstream are cut there. Delegators that already voted on this proposal
+        /// keep their own ballot, unless they were counted in `previous_ballot`.
+        fn ballot_weight(
+            &self,
+            voter: AccountId,
+            proposal: &Proposal,
+            proposal_id: ProposalId,
+            previous_ballot: Option<&Ballot>,
+        ) -> Result<(Balance, Vec<(AccountId, Balance)>), GovernorError> {
+            let weight_cap = self.weight_cap(proposal)?;
+            let weight = (self.voting_power(voter, proposal)?
+                * self
+                    .conviction_locks
+                    .get((proposal_id, voter))
+                    .map_or(1, |lock| lock.conviction.multiplier()))
+            .min(weight_cap);
+            let mut counted_delegators = Vec::new();
+
+            let mut delegates = vec![voter];
+            for _ in 0..MAX_DELEGATION_HOPS {
+                let mut next_delegates = Vec::new();
+                for delegate in delegates {
+                    for delegator in self.delegators.get(delegate).unwrap_or_default() {
+                        next_delegates.push(delegator);
+                        let counted_before = matches!(
+                            previous_ballot,
+                            Some(ballot) if ballot.delegators.contains(&delegator)
+                        );
+                        if self.receipts.contains((proposal_id, delegator))
+                            && !counted_before
+                        {
+                            continue
+                        }
+                        counted_delegators.push((
+                            delegator,
+                            self.voting_power(delegator, proposal)?.min(weight_cap),
+                        ));
+                    }
+                }
+                delegates = next_delegates;
+            }
+            Ok((weight, counted_delegators))
+        }
+
+        /// Votes with the caller's own weight multiplied by `conviction`. The tokens
+        /// backing that weight are escrowed in the governor until the conviction
+        /// periods after `vote_end` have passed, see `unlock`. Requires an allowance
+        /// and a non-zero conviction period.
+        #[ink(message)]
+        pub fn vote_with_conviction(
+            &mut self,
+            proposal_id: ProposalId,
+            vote: VoteType,
+            conviction: Conviction,
+        ) -> Result<(), GovernorError> {
+            if self.conviction_period == 0 {
+                return Err(GovernorError::ConvictionDisabled)
+            }
+            let proposal = self.open_proposal(proposal_id)?;
+            let caller = self.env().caller();
+            if self.conviction_locks.contains((proposal_id, caller)) {
+                return Err(GovernorError::ConvictionAlreadyLocked)
+            }
+
+            let amount = self
+                .balance_of_at(caller, proposal.snapshot())?
+                .min(self.total_supply_at(proposal.snapshot())?);
+            let lock_minutes = conviction
+                .lock_periods()
+                .checked_mul(self.conviction_period)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            let unlock_at = Self::minutes_after(proposal.vote_end, lock_minutes)?;
+            self.transfer_token_from(caller, amount)?;
+            self.locked_tokens = self
+                .locked_tokens
+                .checked_add(amount)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            let lock = ConvictionLock {
+                conviction,
+                amount,
+                unlock_at,
+            };
+            self.conviction_locks.insert((proposal_id, caller), &lock);
+
+            self.cast_vote(caller, proposal_id, vote, None)
+        }
+
+        /// Returns the tokens of an expired conviction lock to the caller.
+        #[ink(message)]
+        pub fn unlock(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<Balance, GovernorError> {
+            let caller = self.env().caller();
+            let lock = self
+                .conviction_locks
+                .get((proposal_id, caller))
+                .ok_or(GovernorError::NoConvictionLock)?;
+            if self.now() < lock.unlock_at {
+                return Err(GovernorError::TokensStillLocked)
+            }
+
+            self.conviction_locks.remove((proposal_id, caller));
+            self.locked_tokens -= lock.amount;
+            self.transfer_token(&TokenTransfer {
+                asset: self.governance_token,
+                to: caller,
+                amount: lock.amount,
+            })?;
+
+            Ok(lock.amount)
+        }
+
+        /// Locks `amount` governance tokens for `duration` minutes, for weight under
+        /// the vote-escrow counting mode. Adding to an existing lock keeps the later
+        /// of both ends. Requires an allowance.
+        #[ink(message)]
+        pub fn lock(
+            &mut self,
+            amount: Balance,
+            duration: u64,
+        ) -> Result<(), GovernorError> {
+            if amount == 0 && duration == 0 {
+                return Err(GovernorError::AmountShouldNotBeZero)
+            }
+            if duration > MAX_VOTE_LOCK {
+                return Err(GovernorError::DurationTooLong)
+            }
+            let caller = self.env().caller();
+            let now = self.now();
+            let mut vote_lock = self.vote_locks.get(caller).unwrap_or(VoteLock {
+                amount: 0,
+                end: now,
+                updated_at: now,
+            });
+
+            let end = Self::minutes_after(now, duration)?;
+            self.transfer_token_from(caller, amount)?;
+            self.locked_tokens = self
+                .locked_tokens
+                .checked_add(amount)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            vote_lock.amount = vote_lock
+                .amount
+                .checked_add(amount)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            vote_lock.end = vote_lock.end.max(end);
+            vote_lock.updated_at = now;
+            self.vote_locks.insert(caller, &vote_lock);
+
+            Ok(())
+        }
+
+        /// Returns the tokens of the caller's vote-escrow lock once it has ended.
+        #[ink(message)]
+        pub fn withdraw_lock(&mut self) -> Result<Balance, GovernorError> {
+            let caller = self.env().caller();
+            let vote_lock = self
+                .vote_locks
+                .get(caller)
+                .ok_or(GovernorError::NoVoteLock)?;
+            if self.now() < vote_lock.end {
+                return Err(GovernorError::TokensStillLocked)
+            }
+
+            self.vote_locks.remove(caller);
+            self.locked_tokens -= vote_lock.amount;
+            self.transfer_token(&TokenTransfer {
+                asset: self.governance_token,
+                to: caller,
+                amount: vote_lock.amount,
+            })?;
+
+            Ok(vote_lock.amount)
+        }
+
+        #[ink(message)]
+        pub fn get_vote_lock(&self, account: AccountId) -> Option<VoteLock> {
+            self.vote_locks.get(account)
+        }
+
+        /// Current vote-escrow weight of `account`.
+        #[ink(message)]
+        pub fn vote_escrow_weight(&self, account: AccountId) -> Balance {
+            self.vote_locks
+                .get(account)
+                .map_or(0, |vote_lock| vote_lock.weight_at(self.now()))
+        }
+
+        #[ink(message)]
+        pub fn get_conviction_lock(
+            &self,
+            proposal_id: ProposalId,
+            account: AccountId,
+        ) -> Option<ConvictionLock> {
+            self.conviction_locks.get((proposal_id, account))
+        }
+
+        /// Withdraws the caller's ballot, and the delegated power counted with it,
+        /// while the vote is still open.
+        #[ink(message)]
+        pub fn retract_vote(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            let proposal = self.open_proposal(proposal_id)?;
+            let caller = self.env().caller();
+            let ballot = self
+                .ballots
+                .get((proposal_id, caller))
+                .ok_or(GovernorError::NoVoteToRetract)?;
+            let mut proposal_vote =
+                self.proposal_votes.get(proposal_id).unwrap_or_default();
+            self.remove_ballot(proposal_id, caller, ballot, &mut proposal_vote)?;
+            self.record_participation(&proposal, caller, false);
+            self.proposal_votes.insert(proposal_id, &proposal_vote);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_ballot(
+            &self,
+            proposal_id: ProposalId,
+            account: AccountId,
+        ) -> Option<Ballot> {
+            self.ballots.get((proposal_id, account))
+        }
+
+        /// Hands the caller's voting power to `to`, who then votes with it on every
+        /// proposal the caller has not voted on yet. Replaces any previous delegate.
+        ///
+        /// If `to` delegates further, the power flows on to the end of the chain,
+        /// which may be at most `MAX_DELEGATION_HOPS` long and must not lead back to
+        /// the caller.
+        #[ink(message)]
+        pub fn delegate(&mut self, to: AccountId) -> Result<(), GovernorError> {
+            let caller = self.env().caller();
+            if to == caller {
+                return Err(GovernorError::SelfDelegation)
+            }
+            let mut hops = 1;
+            let mut next = to;
+            while let Some(delegate) = self.delegates.get(next) {
+                if delegate == caller {
+                    return Err(GovernorError::DelegationCycle)
+                }
+                hops += 1;
+                if hops > MAX_DELEGATION_HOPS {
+                    return Err(GovernorError::DelegationChainTooLong)
+                }
+                next = delegate;
+            }
+            let mut delegators = self.delegators.get(to).unwrap_or_default();
+            if delegators.len() >= MAX_DELEGATORS {
+                return Err(GovernorError::TooManyDelegators)
+            }
+
+            let from_delegate = self.remove_delegation(caller);
+            delegators.push(caller);
+            self.delegators.insert(to, &delegators);
+            self.delegates.insert(caller, &to);
+            self.env().emit_event(DelegateChanged {
+                delegator: caller,
+                from_delegate,
+                to_delegate: Some(to),
+            });
+
+            Ok(())
+        }
+
+        /// Takes the caller's voting power back from its delegate.
+        #[ink(message)]
+        pub fn undelegate(&mut self) -> Result<(), GovernorError> {
+            let caller = self.env().caller();
+            let from_delegate = self
+                .remove_delegation(caller)
+                .ok_or(GovernorError::NotDelegated)?;
+            self.env().emit_event(DelegateChanged {
+                delegator: caller,
+                from_delegate: Some(from_delegate),
+                to_delegate: None,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_delegate(&self, delegator: AccountId) -> Option<AccountId> {
+            self.delegates.get(delegator)
+        }
+
+        /// Account that ends up voting with `account`'s power: the end of its
+        /// delegation chain, or `account` itself if it has not delegated.
+        #[ink(message)]
+        pub fn resolve_delegate(&self, account: AccountId) -> AccountId {
+            let mut delegate = account;
+            for _ in 0..MAX_DELEGATION_HOPS {
+                match self.delegates.get(delegate) {
+                    Some(next) => delegate = next,
+                    None => break,
+                }
+            }
+            delegate
+        }
+
+        #[ink(message)]
+        pub fn get_delegators(&self, delegate: AccountId) -> Vec<AccountId> {
+            self.delegators.get(delegate).unwrap_or_default()
+        }
+
+        /// Ends the voting period of an active proposal once the weight not cast yet
+        /// can no longer change its outcome, returning the decided state.
+        ///
+        /// Only supported for linear counting without a strategy contract and with
+        /// conviction voting disabled, where
+        /// no ballot can weigh more than the tokens behind it.
+        #[ink(message)]
+        pub fn finalize_early(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<ProposalState, GovernorError> {
+            let mut proposal = self.open_proposal(proposal_id)?;
+            if !matches!(proposal.counting_mode, CountingMode::Linear)
+                || proposal.voting_strategy.is_some()
+                || self.conviction_period > 0
+            {
+                return Err(GovernorError::EarlyFinalizationUnsupported)
+            }
+            // Closed before judging, so a decaying quorum is the one that will apply.
+            let now = self.now();
+            proposal.vote_end = now.saturating_sub(1);
+
+            let proposal_vote = self.proposal_votes.get(proposal_id).unwrap_or_default();
+            let cast_votes = proposal_vote
+                .for_votes
+                .saturating_add(proposal_vote.against_votes)
+                .saturating_add(proposal_vote.abstain_votes);
+            let remaining = self.total_weight(&proposal)?.saturating_sub(cast_votes);
+            let worst_case = ProposalVote {
+                against_votes: proposal_vote.against_votes.saturating_add(remaining),
+                ..proposal_vote
+            };
+            let best_case = ProposalVote {
+                for_votes: proposal_vote.for_votes.saturating_add(remaining),
+                ..proposal_vote
+            };
+            let state = if self.quorum_reached(&proposal, &proposal_vote)?
+                && Self::vote_succeeded(&worst_case)
+                && self.approval_threshold_met(&proposal, &worst_case)
+            {
+                ProposalState::Succeeded
+            } else if !Self::vote_succeeded(&best_case)
+                || !self.approval_threshold_met(&proposal, &best_case)
+            {
+                ProposalState::Defeated
+            } else {
+                return Err(GovernorError::OutcomeNotDecided)
+            };
+
+            proposal.expires_at = Self::minutes_after(now, self.config.grace_period)?;
+            self.proposals.insert(proposal_id, &proposal);
+
+            Ok(state)
+        }
+
+        /// Starts the timelock of a succeeded proposal. It can be executed once
+        /// `timelock_delay` minutes have passed, until the grace period runs out.
+        #[ink(message)]
+        pub fn queue(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            self.ensure_succeeded(proposal_id, &proposal)?;
+            if proposal.eta.is_some() {
+                return Err(GovernorError::ProposalAlreadyQueued)
+            }
+
+            let timelock_delay = match proposal.track {
+                Some(track) => track.timelock_delay,
+                None => self.config.timelock_delay,
+            };
+            let timelock_delay = match proposal.kind {
+                ProposalKind::EmergencyWithdrawal(_) => {
+                    timelock_delay.min(self.emergency.timelock_delay)
+                }
+                _ => timelock_delay,
+            };
+            let eta = Self::minutes_after(self.now(), timelock_delay)?;
+            proposal.eta = Some(eta);
+            proposal.expires_at = Self::minutes_after(eta, self.config.grace_period)?;
+            self.proposals.insert(proposal_id, &proposal);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn execute(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            self.entered.set(&true);
+            let result = self.execute_proposal(proposal_id);
+            self.entered.set(&false);
+            result
+        }
+
+        fn execute_proposal(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_not_paused()?;
+            self.ensure_kill_switch_off()?;
+            self.ensure_role(Role::Executor, self.env().caller())?;
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            self.ensure_succeeded(proposal_id, &proposal)?;
+            match proposal.eta {
+                None => return Err(GovernorError::ProposalNotQueued),
+                Some(eta) if self.now() < eta => {
+                    return Err(GovernorError::TimelockNotElapsed)
+                }
+                Some(_) => {}
+            }
+            self.ensure_recipients_allowed(&proposal.kind)?;
+            if self.objection_window_open(&proposal) {
+                return Err(GovernorError::ObjectionWindowOpen)
+            }
+            if self.signatures_missing(proposal_id, &proposal.kind) {
+                return Err(GovernorError::SignaturesMissing)
+            }
+            let outflow = self.native_outflow(proposal_id, &proposal.kind);
+            self.spend_budget(outflow)?;
+
+            // Marked before any funds move, so nothing the proposal pays or calls
+            // can find it executable again.
+            proposal.executed = true;
+            self.proposals.insert(proposal_id, &proposal);
+            if let Err(error) = self.apply_proposal(proposal_id, &proposal) {
+                proposal.executed = false;
+                self.proposals.insert(proposal_id, &proposal);
+                return Err(error)
+            }
+            self.env().emit_event(ProposalExecuted { proposal_id });
+
+            Ok(())
+        }
+
+        /// Carries out an executed proposal. Bookkeeping is written before each
+        /// transfer or call; on error the message reverts whatever was written.
+        fn apply_proposal(
+            &mut self,
+            proposal_id: ProposalId,
+            proposal: &Proposal,
+        ) -> Result<(), GovernorError> {
+            match &proposal.kind {
+                ProposalKind::NativeTransfer(transfers) => {
+                    for transfer in transfers.iter() {
+                        self.record_disbursement(proposal_id, transfer.amount);
+                        self.pay(
+                            transfer.to,
+                            transfer.amount,
+                            Movement::Payout,
+                            Some(proposal_id),
+                        )?;
+                        self.env().emit_event(PayoutSent {
+                            proposal_id,
+                            to: transfer.to,
+                            asset: None,
+                            amount: transfer.amount,
+                        });
+                    }
+                }
+                ProposalKind::TokenTransfer(transfers) => {
+                    for transfer in transfers.iter() {
+                        self.ensure_unlocked_tokens(transfer)?;
+                        self.log_movement(
+                            Movement::Payout,
+                            false,
+                            transfer.to,
+                            Some(transfer.asset),
+                            transfer.amount,
+                            Some(proposal_id),
+                        );
+                        self.transfer_token(transfer)?;
+                        self.env().emit_event(PayoutSent {
+                            proposal_id,
+                            to: transfer.to,
+                            asset: Some(transfer.asset),
+                            amount: transfer.amount,
+                        });
+                    }
+                }
+                ProposalKind::GenericCall(call)
+                    if call.callee == self.env().account_id() =>
+                {
+                    self.apply_self_call(call)?
+                }
+                ProposalKind::GenericCall(call) => self.dispatch_call(call)?,
+                ProposalKind::ParamChange(params) => {
+                    params.iter().for_each(|param| self.set_param(param));
+                    if self.config.min_duration > self.config.max_duration {
+                        return Err(GovernorError::InvalidParameter)
+                    }
+                }
+                ProposalKind::Upgrade(code_hash) => self.upgrade(code_hash)?,
+                ProposalKind::Recurring(payout) => {
+                    let schedule = PayoutSchedule {
+                        payout: payout.clone(),
+                        start: self.now(),
+                        claimed_periods: 0,
+                    };
+                    self.payout_schedules.insert(proposal_id, &schedule);
+                }
+                ProposalKind::Milestones(grant) => {
+                    let progress = MilestoneProgress {
+                        grant: grant.clone(),
+                        released: 0,
+                    };
+                    self.milestone_grants.insert(proposal_id, &progress);
+                }
+                ProposalKind::ReleaseMilestone(grant_id) => {
+                    self.release_milestone(*grant_id)?;
+                }
+                ProposalKind::MultiChoice(choice) => {
+                    let winner = self
+                        .choice_winner(proposal_id)
+                        .ok_or(GovernorError::ProposalNotAccepted)?;
+                    let payout = &choice.options[winner as usize].payout;
+                    self.record_disbursement(proposal_id, payout.amount);
+                    self.pay(
+                        payout.to,
+                        payout.amount,
+                        Movement::Payout,
+                        Some(proposal_id),
+                    )?;
+                }
+                ProposalKind::FundRewards(funding) => {
+                    if self.reward_epoch == 0 {
+                        return Err(GovernorError::InvalidParameter)
+                    }
+                    if self.epoch_settled(funding.epoch)? {
+                        return Err(GovernorError::EpochSettled)
+                    }
+                    if funding.amount > self.treasury_balance() {
+                        return Err(GovernorError::InsufficientFunds)
+                    }
+                    let pool = self
+                        .reward_pools
+                        .get(funding.epoch)
+                        .unwrap_or_default()
+                        .checked_add(funding.amount)
+                        .ok_or(GovernorError::ArithmeticOverflow)?;
+                    self.reward_pools.insert(funding.epoch, &pool);
+                    self.reserved_rewards = self
+                        .reserved_rewards
+                        .checked_add(funding.amount)
+                        .ok_or(GovernorError::ArithmeticOverflow)?;
+                }
+                ProposalKind::Distribute(amount) => {
+                    self.distribute(proposal_id, proposal, *amount)?
+                }
+                ProposalKind::Vesting(grant) => {
+                    let mut grants =
+                        self.vesting_grants.get(grant.to).unwrap_or_default();
+                    if grants.len() >= MAX_VESTING_GRANTS {
+                        return Err(GovernorError::TooManyGrants)
+                    }
+                    grants.push(proposal_id);
+                    self.vesting_grants.insert(grant.to, &grants);
+                    let start = self.now();
+                    let vesting = Vesting {
+                        to: grant.to,
+                        amount: grant.amount,
+                        start,
+                        cliff: Self::minutes_after(start, grant.cliff)?,
+                        end: Self::minutes_after(start, grant.duration)?,
+                        claimed: 0,
+                    };
+                    self.vestings.insert(proposal_id, &vesting);
+                    self.reserve_grant(grant.amount)?;
+                }
+                ProposalKind::OpenRound(terms) => {
+                    let round = Round {
+                        terms: *terms,
+                        deadline: Self::minutes_after(self.now(), terms.duration)?,
+                        raised: 0,
+                    };
+                    self.rounds.insert(proposal_id, &round);
+                }
+                ProposalKind::Stream(payout) => {
+                    let start = self.now();
+                    let stream = Stream {
+                        to: payout.to,
+                        amount: payout.amount,
+                        start,
+                        end: Self::minutes_after(start, payout.duration)?,
+                        withdrawn: 0,
+                    };
+                    self.streams.insert(proposal_id, &stream);
+                    self.reserve_grant(payout.amount)?;
+                }
+                ProposalKind::Hire(payout) => {
+                    let employed = self
+                        .payroll
+                        .get(payout.to)
+                        .and_then(|hire_id| self.payout_schedules.get(hire_id));
+                    if matches!(
+                        employed,
+                        Some(schedule) if schedule.claimed_periods < schedule.payout.periods
+                    ) {
+                        return Err(GovernorError::AlreadyOnPayroll)
+                    }
+                    let schedule = PayoutSchedule {
+                        payout: payout.clone(),
+                        start: self.now(),
+                        claimed_periods: 0,
+                    };
+                    self.payout_schedules.insert(proposal_id, &schedule);
+                    self.payroll.insert(payout.to, &proposal_id);
+                }
+                ProposalKind::Dismiss(contributor) => {
+                    let hire_id = self
+                        .payroll
+                        .take(*contributor)
+                        .ok_or(GovernorError::NotOnPayroll)?;
+                    let mut schedule = self
+                        .payout_schedules
+                        .get(hire_id)
+                        .ok_or(GovernorError::ScheduleNotFound)?;
+                    let unlocked_periods = self.unlocked_periods(&schedule)?;
+                    let owed = schedule
+                        .payout
+                        .amount
+                        .checked_mul(
+                            (unlocked_periods - schedule.claimed_periods) as Balance,
+                        )
+                        .ok_or(GovernorError::ArithmeticOverflow)?;
+                    schedule.payout.periods = unlocked_periods;
+                    schedule.claimed_periods = unlocked_periods;
+                    self.payout_schedules.insert(hire_id, &schedule);
+                    if owed > 0 {
+                        self.record_disbursement(hire_id, owed);
+                        self.pay(*contributor, owed, Movement::Payout, Some(hire_id))?;
+                    }
+                }
+                ProposalKind::Escrow(transfer) => {
+                    if self.treasury_balance() < transfer.amount {
+                        return Err(GovernorError::InsufficientFunds)
+                    }
+                    let escrow = Escrow {
+                        transfer: transfer.clone(),
+                        settled: false,
+                    };
+                    self.escrows.insert(proposal_id, &escrow);
+                    self.escrowed = self
+                        .escrowed
+                        .checked_add(transfer.amount)
+                        .ok_or(GovernorError::ArithmeticOverflow)?;
+                }
+                ProposalKind::ReleaseEscrow(escrow_id) => {
+                    let mut escrow = self.unsettled_escrow(*escrow_id)?;
+                    escrow.settled = true;
+                    self.escrows.insert(escrow_id, &escrow);
+                    self.escrowed -= escrow.transfer.amount;
+                    self.record_disbursement(*escrow_id, escrow.transfer.amount);
+                    self.pay(
+                        escrow.transfer.to,
+                        escrow.transfer.amount,
+                        Movement::Payout,
+                        Some(*escrow_id),
+                    )?;
+                }
+                ProposalKind::Swap(swap) => {
+                    self.swap(swap)?;
+                }
+                ProposalKind::Buyback(buyback) => {
+                    let this = self.env().account_id();
+                    self.ensure_unlocked_tokens(&TokenTransfer {
+                        asset: self.governance_token,
+                        to: this,
+                        amount: buyback.contributed,
+                    })?;
+                    let bought = if buyback.amount > 0 {
+                        self.swap(&Swap {
+                            asset_in: None,
+                            asset_out: Some(self.governance_token),
+                            amount_in: buyback.amount,
+                            min_amount_out: buyback.min_tokens_out,
+                        })?
+                    } else {
+                        0
+                    };
+                    self.burn_token(this, bought + buyback.contributed)?;
+                }
+                ProposalKind::Stake(amount) => {
+                    let adapter = self
+                        .staking_adapter
+                        .ok_or(GovernorError::NoStakingAdapter)?;
+                    if self.treasury_balance() < *amount {
+                        return Err(GovernorError::InsufficientFunds)
+                    }
+                    self.staked = self
+                        .staked
+                        .checked_add(*amount)
+                        .ok_or(GovernorError::ArithmeticOverflow)?;
+                    self.stake(adapter, *amount)?;
+                }
+                ProposalKind::Unstake(amount) => {
+                    let adapter = self
+                        .staking_adapter
+                        .ok_or(GovernorError::NoStakingAdapter)?;
+                    if self.staked < *amount {
+                        return Err(GovernorError::InsufficientStake)
+                    }
+                    self.staked -= amount;
+                    let before = self.env().balance();
+                    self.unstake(adapter, *amount)?;
+                    let received = self.env().balance().saturating_sub(before);
+                    self.staking_rewards += received.saturating_sub(*amount);
+                }
+                ProposalKind::CancelStream(stream_id) => {
+                    self.cancel_stream(*stream_id)?
+                }
+                ProposalKind::EmergencyWithdrawal(recovery) => {
+                    self.sweep_treasury(proposal_id, *recovery)?
+                }
+                ProposalKind::Sunset => {
+                    let distributed = self.treasury_balance();
+                    if distributed > 0 {
+                        self.distribute(proposal_id, proposal, distributed)?;
+                    }
+                    self.terminated = true;
+                    self.env().emit_event(GovernorTerminated {
+                        proposal_id,
+                        distributed,
+                    });
+                }
+                ProposalKind::Clawback(grant_id) => {
+                    if self.streams.contains(grant_id) {
+                        self.cancel_stream(*grant_id)?;
+                    } else {
+                        self.claw_back_vesting(*grant_id)?;
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Once voting has ended, refunds the proposer's bond if the proposal reached
+        /// quorum, or keeps it in the treasury otherwise. Anyone may settle.
+        #[ink(message)]
+        pub fn settle_bond(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if proposal.bond_settled {
+                return Err(GovernorError::BondAlreadySettled)
+            }
+            if self.now() <= proposal.vote_end {
+                return Err(GovernorError::VotePeriodNotEnded)
+            }
+
+            proposal.bond_settled = true;
+            self.proposals.insert(proposal_id, &proposal);
+            self.reserved_bonds -= proposal.bond;
+            let proposal_vote = self.proposal_votes.get(proposal_id).unwrap_or_default();
+            if self.quorum_reached(&proposal, &proposal_vote)? && proposal.bond > 0 {
+                self.pay(
+                    proposal.proposer,
+                    proposal.bond,
+                    Movement::Refund,
+                    Some(proposal_id),
+                )?;
+            }
+
+            Ok(())
+        }
+
+        /// Removes executed, defeated, expired or canceled proposals and their votes
+        /// once the retention period after their grace period has passed. Proposals
+        /// that cannot be pruned yet, or hold an unsettled bond, are skipped.
+        /// Returns how many proposals were removed.
+        #[ink(message)]
+        pub fn prune(&mut self, proposal_ids: Vec<ProposalId>) -> u32 {
+            let mut pruned = 0;
+            for proposal_id in proposal_ids {
+                let Some(proposal) = self.proposals.get(proposal_id) else {
+                    continue
+                };
+                let finished = matches!(
+                    self.get_state(proposal_id),
+                    Ok(ProposalState::Executed
+                        | ProposalState::Defeated
+                        | ProposalState::Expired
+                        | ProposalState::Canceled)
+                );
+                let Ok(retained_until) =
+                    Self::minutes_after(proposal.expires_at, self.retention_period)
+                else {
+                    continue
+                };
+                let bond_pending = proposal.bond > 0 && !proposal.bond_settled;
+                if !finished || self.now() <= retained_until || bond_pending {
+                    continue
+                }
+
+                for voter in self.voters.take(proposal_id).unwrap_or_default() {
+                    self.receipts.remove((proposal_id, voter));
+                    self.ballots.remove((proposal_id, voter));
+                    self.choice_ballots.remove((proposal_id, voter));
+                }
+                self.proposal_votes.remove(proposal_id);
+                self.proposals.remove(proposal_id);
+                self.env().emit_event(ProposalPruned { proposal_id });
+                pruned += 1;
+            }
+            pruned
+        }
+
+        /// Pays the recipient of an executed recurring proposal every tranche that
+        /// has unlocked since their last claim.
+        #[ink(message)]
+        pub fn claim_recurring(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            let mut schedule = self
+                .payout_schedules
+                .get(proposal_id)
+                .ok_or(GovernorError::ScheduleNotFound)?;
+            if self.env().caller() != schedule.payout.to {
+                return Err(GovernorError::NotRecipient)
+            }
+
+            let unlocked_periods = self.unlocked_periods(&schedule)?;
+            if unlocked_periods <= schedule.claimed_periods {
+                return Err(GovernorError::NothingToClaim)
+            }
+
+            let amount = schedule
+                .payout
+                .amount
+                .checked_mul((unlocked_periods - schedule.claimed_periods) as Balance)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            schedule.claimed_periods = unlocked_periods;
+            self.payout_schedules.insert(proposal_id, &schedule);
+            self.pay(
+                schedule.payout.to,
+                amount,
+                Movement::Payout,
+                Some(proposal_id),
+            )?;
+            self.record_disbursement(proposal_id, amount);
+
+            Ok(amount)
+        }
+
+        /// Buys into the DAO: the native deposit goes to the treasury and the caller
+        /// is minted `join_rate` governance tokens per unit deposited. The governor
+        /// must be allowed to mint on the governance token. Returns the tokens
+        /// minted.
+        #[ink(message, payable)]
+        pub fn join(&mut self) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            self.ensure_verified(self.env().caller())?;
+            if self.join_rate == 0 {
+                return Err(GovernorError::JoiningDisabled)
+            }
+            let deposit = self.env().transferred_value();
+            Self::validate_amount(deposit)?;
+
+            let shares = deposit
+                .checked_mul(self.join_rate)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            self.mint_token(self.env().caller(), shares)?;
+            self.log_movement(
+                Movement::Deposit,
+                true,
+                self.env().caller(),
+                None,
+                deposit,
+                None,
+            );
+            self.issue_receipt(self.env().caller(), deposit)?;
+
+            Ok(shares)
+        }
+
+        #[ink(message)]
+        pub fn join_rate(&self) -> Balance {
+            self.join_rate
+        }
+
+        /// Contributes the transferred value to an open fundraising round, minting
+        /// governance tokens to the caller at the round rate. The governor must be
+        /// allowed to mint on the governance token. Returns the tokens minted.
+        #[ink(message, payable)]
+        pub fn contribute(
+            &mut self,
+            round_id: ProposalId,
+        ) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            self.ensure_verified(self.env().caller())?;
+            let mut round = self
+                .rounds
+                .get(round_id)
+                .ok_or(GovernorError::RoundNotFound)?;
+            if !round.is_open(self.now()) {
+                return Err(GovernorError::RoundClosed)
+            }
+            let deposit = self.env().transferred_value();
+            Self::validate_amount(deposit)?;
+            let raised = round
+                .raised
+                .checked_add(deposit)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            if raised > round.terms.hard_cap {
+                return Err(GovernorError::HardCapExceeded)
+            }
+
+            let contributor = self.env().caller();
+            let shares = deposit
+                .checked_mul(round.terms.rate)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            self.mint_token(contributor, shares)?;
+            if raised < round.terms.soft_cap {
+                self.raising = self
+                    .raising
+                    .checked_add(deposit)
+                    .ok_or(GovernorError::ArithmeticOverflow)?;
+            } else if round.raised < round.terms.soft_cap {
+                self.raising -= round.raised;
+            }
+            round.raised = raised;
+            self.rounds.insert(round_id, &round);
+            let mut contribution = self
+                .round_contributions
+                .get((round_id, contributor))
+                .unwrap_or_default();
+            contribution.deposited = contribution
+                .deposited
+                .checked_add(deposit)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            contribution.minted = contribution
+                .minted
+                .checked_add(shares)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            self.log_movement(
+                Movement::Deposit,
+                true,
+                contributor,
+                None,
+                deposit,
+                Some(round_id),
+            );
+            self.round_contributions
+                .insert((round_id, contributor), &contribution);
+            self.issue_receipt(contributor, deposit)?;
+
+            Ok(shares)
+        }
+
+        /// Returns the caller's deposits to a round that closed below its soft cap,
+        /// burning the tokens minted for them. Returns the amount refunded.
+        #[ink(message)]
+        pub fn refund(&mut self, round_id: ProposalId) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            let round = self
+                .rounds
+                .get(round_id)
+                .ok_or(GovernorError::RoundNotFound)?;
+            if round.is_open(self.now()) || round.raised >= round.terms.soft_cap {
+                return Err(GovernorError::RoundNotFailed)
+            }
+            let caller = self.env().caller();
+            let contribution = self
+                .round_contributions
+                .get((round_id, caller))
+                .ok_or(GovernorError::NothingToRefund)?;
+
+            self.burn_token(caller, contribution.minted)?;
+            self.round_contributions.remove((round_id, caller));
+            self.raising -= contribution.deposited;
+            self.pay(
+                caller,
+                contribution.deposited,
+                Movement::Refund,
+                Some(round_id),
+            )?;
+
+            Ok(contribution.deposited)
+        }
+
+        #[ink(message)]
+        pub fn get_round(&self, round_id: ProposalId) -> Option<Round> {
+            self.rounds.get(round_id)
+        }
+
+        #[ink(message)]
+        pub fn get_round_contribution(
+            &self,
+            round_id: ProposalId,
+            contributor: AccountId,
+        ) -> RoundContribution {
+            self.round_contributions
+                .get((round_id, contributor))
+                .unwrap_or_default()
+        }
+
+        /// Donates the transferred value to the treasury, recording the donor.
+        #[ink(message, payable)]
+        pub fn donate(&mut self) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            self.ensure_verified(self.env().caller())?;
+            let amount = self.env().transferred_value();
+            Self::validate_amount(amount)?;
+
+            let donor = self.env().caller();
+            let mut donation = match self.donations.get(donor) {
+                Some(donation) => donation,
+                None => {
+                    self.donors.insert(self.donor_count, &donor);
+                    self.donor_count = self
+                        .donor_count
+                        .checked_add(1)
+                        .ok_or(GovernorError::ArithmeticOverflow)?;
+                    Donation::default()
+                }
+            };
+            donation.total = donation
+                .total
+                .checked_add(amount)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            donation.count += 1;
+            donation.last_at = self.env().block_timestamp();
+            self.donations.insert(donor, &donation);
+            self.log_movement(Movement::Donation, true, donor, None, amount, None);
+            self.issue_receipt(donor, amount)?;
+
+            self.env().emit_event(DonationReceived { donor, amount });
+            Ok(())
+        }
+
+        /// Up to `limit` treasury movements from index `start`, oldest first. At
+        /// most `MAX_LEDGER_PAGE` are returned per call.
+        #[ink(message)]
+        pub fn get_ledger(&self, start: u64, limit: u32) -> Vec<LedgerEntry> {
+            let end = self
+                .ledger_len
+                .min(start.saturating_add(limit.min(MAX_LEDGER_PAGE) as u64));
+            (start..end)
+                .filter_map(|index| self.ledger.get(index))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn ledger_len(&self) -> u64 {
+            self.ledger_len
+        }
+
+        #[ink(message)]
+        pub fn get_donation(&self, donor: AccountId) -> Option<Donation> {
+            self.donations.get(donor)
+        }
+
+        /// Up to `limit` donors from index `start`, in order of their first
+        /// donation. At most `MAX_LEDGER_PAGE` are returned per call.
+        #[ink(message)]
+        pub fn get_donors(&self, start: u32, limit: u32) -> Vec<AccountId> {
+            let end = self
+                .donor_count
+                .min(start.saturating_add(limit.min(MAX_LEDGER_PAGE)));
+            (start..end)
+                .filter_map(|index| self.donors.get(index))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn donor_count(&self) -> u32 {
+            self.donor_count
+        }
+
+        #[ink(message)]
+        pub fn get_contribution_receipt(
+            &self,
+            receipt_id: u32,
+        ) -> Option<ContributionReceipt> {
+            self.contribution_receipts.get(receipt_id)
+        }
+
+        /// PSP34 contract contribution receipts are minted on, if any.
+        #[ink(message)]
+        pub fn receipt_nft(&self) -> Option<AccountId> {
+            self.receipt_nft
+        }
+
+        /// Contribution thresholds of the receipt NFT tiers.
+        #[ink(message)]
+        pub fn receipt_tiers(&self) -> Vec<Balance> {
+            self.receipt_tiers.clone()
+        }
+
+        /// Registers a PSP22 asset held by the treasury. Admin or treasurers only.
+        #[ink(message)]
+        pub fn add_asset(&mut self, asset: AccountId) -> Result<(), GovernorError> {
+            self.ensure_treasurer()?;
+            if asset == self.governance_token {
+                return Err(GovernorError::InvalidParameter)
+            }
+            if self.assets.contains(&asset) {
+                return Err(GovernorError::AssetAlreadyRegistered)
+            }
+            if self.assets.len() >= MAX_ASSETS {
+                return Err(GovernorError::TooManyAssets)
+            }
+            self.assets.push(asset);
+            Ok(())
+        }
+
+        /// Removes a PSP22 asset from the treasury registry. Admin or treasurers
+        /// only.
+        #[ink(message)]
+        pub fn remove_asset(&mut self, asset: AccountId) -> Result<(), GovernorError> {
+            self.ensure_treasurer()?;
+            let index = self
+                .assets
+                .iter()
+                .position(|registered| *registered == asset)
+                .ok_or(GovernorError::AssetNotRegistered)?;
+            self.assets.swap_remove(index);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_assets(&self) -> Vec<AccountId> {
+            self.assets.clone()
+        }
+
+        /// Native funds proposals may still commit in the current budget epoch, if a
+        /// budget is set.
+        #[ink(message)]
+        pub fn remaining_budget(&self) -> Option<Balance> {
+            if self.budget_cap == 0 {
+                return None
+            }
+            Some(self.current_budget())
+        }
+
+        /// Native funds held by the treasury, without unclaimed dividends, rewards,
+        /// grants and unsettled proposal bonds.
+        #[ink(message)]
+        pub fn treasury_balance(&self) -> Balance {
+            self.env()
+                .balance()
+                .saturating_sub(self.reserved_dividends)
+                .saturating_sub(self.reserved_rewards)
+                .saturating_sub(self.reserved_grants)
+                .saturating_sub(self.reserved_bonds)
+                .saturating_sub(self.escrowed)
+                .saturating_sub(self.raising)
+        }
+
+        /// Returns the funds of an escrow to the treasury once `release_id`, a
+        /// proposal to release them, was defeated or expired. Anyone may call it.
+        #[ink(message)]
+        pub fn refund_escrow(
+            &mut self,
+            escrow_id: ProposalId,
+            release_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let mut escrow = self.unsettled_escrow(escrow_id)?;
+            let release = self
+                .proposals
+                .get(release_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if !matches!(release.kind, ProposalKind::ReleaseEscrow(id) if id == escrow_id)
+            {
+                return Err(GovernorError::InvalidProposalKind)
+            }
+            if !matches!(
+                self.get_state(release_id)?,
+                ProposalState::Defeated | ProposalState::Expired
+            ) {
+                return Err(GovernorError::ReleaseNotDefeated)
+            }
+
+            escrow.settled = true;
+            self.escrows.insert(escrow_id, &escrow);
+            self.escrowed -= escrow.transfer.amount;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_escrow(&self, escrow_id: ProposalId) -> Option<Escrow> {
+            self.escrows.get(escrow_id)
+        }
+
+        /// Pays capital or profits of an investment back into the treasury,
+        /// attributed to the proposal that disbursed it.
+        #[ink(message, payable)]
+        pub fn record_return(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let amount = self.env().transferred_value();
+            Self::validate_amount(amount)?;
+            let mut investment = self
+                .investments
+                .get(proposal_id)
+                .ok_or(GovernorError::NotAnInvestment)?;
+            investment.returned += amount;
+            self.investments.insert(proposal_id, &investment);
+            self.portfolio.returned += amount;
+            self.log_movement(
+                Movement::Return,
+                true,
+                self.env().caller(),
+                None,
+                amount,
+                Some(proposal_id),
+            );
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_investment(&self, proposal_id: ProposalId) -> Option<Investment> {
+            self.investments.get(proposal_id)
+        }
+
+        /// Totals over every investment, whose `roi` is that of the whole portfolio.
+        #[ink(message)]
+        pub fn get_portfolio(&self) -> Investment {
+            self.portfolio.clone()
+        }
+
+        /// Return of a proposal's payouts in basis points, if it disbursed funds.
+        #[ink(message)]
+        pub fn investment_roi(&self, proposal_id: ProposalId) -> Option<i128> {
+            self.investments.get(proposal_id)?.roi()
+        }
+
+        /// Pays the caller's dividend from a distribution, pro rata to their
+        /// governance token balance at its snapshot.
+        #[ink(message)]
+        pub fn claim_dividend(
+            &mut self,
+            distribution_id: DistributionId,
+        ) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            let distribution = self
+                .distributions
+                .get(distribution_id)
+                .ok_or(GovernorError::DistributionNotFound)?;
+            let caller = self.env().caller();
+            if self.dividend_claims.contains((distribution_id, caller)) {
+                return Err(GovernorError::DividendAlreadyClaimed)
+            }
+            let dividend = self.dividend_of(caller, &distribution)?;
+            if dividend == 0 {
+                return Err(GovernorError::NothingToClaim)
+            }
+
+            self.dividend_claims.insert((distribution_id, caller), &());
+            self.reserved_dividends -= dividend;
+            self.pay(
+                caller,
+                dividend,
+                Movement::Dividend,
+                Some(distribution.proposal_id),
+            )?;
+
+            Ok(dividend)
+        }
+
+        /// Dividend `account` can still claim from a distribution.
+        #[ink(message)]
+        pub fn claimable(
+            &self,
+            account: AccountId,
+            distribution_id: DistributionId,
+        ) -> Balance {
+            match self.distributions.get(distribution_id) {
+                Some(distribution)
+                    if !self.dividend_claims.contains((distribution_id, account)) =>
+                {
+                    self.dividend_of(account, &distribution).unwrap_or_default()
+                }
+                _ => 0,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_distribution(
+            &self,
+            distribution_id: DistributionId,
+        ) -> Option<Distribution> {
+            self.distributions.get(distribution_id)
+        }
+
+        /// Treasury balance of a registered asset, or of the governance token
+        /// without the tokens escrowed for voters.
+        #[ink(message)]
+        pub fn treasury_balance_of(
+            &self,
+            asset: AccountId,
+        ) -> Result<Balance, GovernorError> {
+            let balance = self.asset_balance_of(asset, self.env().account_id())?;
+            if asset == self.governance_token {
+                return Ok(balance.saturating_sub(self.locked_tokens))
+            }
+            if !self.assets.contains(&asset) {
+                return Err(GovernorError::AssetNotRegistered)
+            }
+            Ok(balance)
+        }
+
+        /// Leaves the DAO: burns `amount` of the caller's governance tokens and pays
+        /// them the same share of the native treasury and of every registered
+        /// asset. Refused while a proposal the
+        /// caller's power counted on has not reached a final state. The governor
+        /// must be allowed to burn on the governance token. Returns the payout.
+        #[ink(message)]
+        pub fn rage_quit(&mut self, amount: Balance) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            self.entered.set(&true);
+            let result = self.exit_with(amount);
+            self.entered.set(&false);
+            result
+        }
+
+        fn exit_with(&mut self, amount: Balance) -> Result<Balance, GovernorError> {
+            Self::validate_amount(amount)?;
+            let caller = self.env().caller();
+            if !self.unsettled_votes(caller).is_empty() {
+                return Err(GovernorError::VotesPending)
+            }
+            if self.balance_of(caller)? < amount {
+                return Err(GovernorError::InsufficientTokenBalance)
+            }
+
+            let total_supply = self.total_supply()?;
+            // Funds owed to grantees and voters are not part of the treasury.
+            let share = Self::mul_div(self.treasury_balance(), amount, total_supply)?;
+            self.voted_proposals.remove(caller);
+            self.burn_token(caller, amount)?;
+            self.pay(caller, share, Movement::RageQuit, None)?;
+            for asset in self.assets.clone() {
+                let asset_share = Self::mul_div(
+                    self.asset_balance_of(asset, self.env().account_id())?,
+                    amount,
+                    total_supply,
+                )?;
+                if asset_share > 0 {
+                    self.transfer_token(&TokenTransfer {
+                        asset,
+                        to: caller,
+                        amount: asset_share,
+                    })?;
+                    self.log_movement(
+                        Movement::RageQuit,
+                        false,
+                        caller,
+                        Some(asset),
+                        asset_share,
+                        None,
+                    );
+                }
+            }
+
+            Ok(share)
+        }
+
+        /// Pays the caller's share of an epoch's participation rewards, pro rata to
+        /// the proposals they voted on. Claims open once every proposal whose
+        /// voting started in the epoch has closed.
+        #[ink(message)]
+        pub fn claim_rewards(&mut self, epoch: u64) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            if !self.epoch_settled(epoch)? {
+                return Err(GovernorError::EpochNotSettled)
+            }
+            let caller = self.env().caller();
+            let participation = self.participation.take((epoch, caller)).unwrap_or(0);
+            let total = self.epoch_participation.get(epoch).unwrap_or(0);
+            let pool = self.reward_pools.get(epoch).unwrap_or(0);
+            if participation == 0 || pool == 0 {
+                return Err(GovernorError::NothingToClaim)
+            }
+
+            let amount = pool * participation as Balance / total as Balance;
+            self.reserved_rewards -= amount;
+            self.pay(caller, amount, Movement::Reward, None)?;
+
+            Ok(amount)
+        }
+
+        #[ink(message)]
+        pub fn get_participation(&self, epoch: u64, account: AccountId) -> u32 {
+            self.participation.get((epoch, account)).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_reward_pool(&self, epoch: u64) -> Balance {
+            self.reward_pools.get(epoch).unwrap_or(0)
+        }
+
+        /// Pays the recipient of an executed stream what has vested since their
+        /// last withdrawal.
+        #[ink(message)]
+        pub fn withdraw_stream(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            let mut stream = self
+                .streams
+                .get(proposal_id)
+                .ok_or(GovernorError::StreamNotFound)?;
+            if self.env().caller() != stream.to {
+                return Err(GovernorError::NotRecipient)
+            }
+            let amount = stream.vested_at(self.now()) - stream.withdrawn;
+            if amount == 0 {
+                return Err(GovernorError::NothingToClaim)
+            }
+
+            stream.withdrawn += amount;
+            self.streams.insert(proposal_id, &stream);
+            self.reserved_grants -= amount;
+            self.pay(stream.to, amount, Movement::Payout, Some(proposal_id))?;
+            self.record_disbursement(proposal_id, amount);
+
+            Ok(amount)
+        }
+
+        #[ink(message)]
+        pub fn get_stream(&self, proposal_id: ProposalId) -> Option<Stream> {
+            self.streams.get(proposal_id)
+        }
+
+        /// Pays the caller what has vested on all their grants since their last
+        /// claim. Returns the amount paid.
+        #[ink(message)]
+        pub fn claim_vested(&mut self) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            let now = self.now();
+            let mut grants = self.vesting_grants.get(caller).unwrap_or_default();
+            let mut total: Balance = 0;
+            let mut index = 0;
+            while index < grants.len() {
+                let grant_id = grants[index];
+                let mut vesting = self
+                    .vestings
+                    .get(grant_id)
+                    .ok_or(GovernorError::GrantNotFound)?;
+                let amount = vesting.vested_at(now) - vesting.claimed;
+                if amount > 0 {
+                    vesting.claimed += amount;
+                    self.vestings.insert(grant_id, &vesting);
+                    self.record_disbursement(grant_id, amount);
+                    total += amount;
+                }
+                if vesting.claimed == vesting.amount {
+                    grants.swap_remove(index);
+                } else {
+                    index += 1;
+                }
+            }
+            if total == 0 {
+                return Err(GovernorError::NothingToClaim)
+            }
+
+            self.vesting_grants.insert(caller, &grants);
+            self.reserved_grants -= total;
+            self.pay(caller, total, Movement::Payout, None)?;
+            Ok(total)
+        }
+
+        #[ink(message)]
+        pub fn get_vesting(&self, proposal_id: ProposalId) -> Option<Vesting> {
+            self.vestings.get(proposal_id)
+        }
+
+        /// Vesting grants of an account that still have funds to claim.
+        #[ink(message)]
+        pub fn get_vesting_grants(&self, account: AccountId) -> Vec<ProposalId> {
+            self.vesting_grants.get(account).unwrap_or_default()
+        }
+
+        /// Pays the caller the salary of every period started since their last
+        /// claim.
+        #[ink(message)]
+        pub fn claim_salary(&mut self) -> Result<Balance, GovernorError> {
+            self.ensure_not_entered()?;
+            let hire_id = self
+                .payroll
+                .get(self.env().caller())
+                .ok_or(GovernorError::NotOnPayroll)?;
+            self.claim_recurring(hire_id)
+        }
+
+        /// `Hire` proposal currently paying `contributor`, if any.
+        #[ink(message)]
+        pub fn get_salary(&self, contributor: AccountId) -> Option<ProposalId> {
+            self.payroll.get(contributor)
+        }
+
+        #[ink(message)]
+        pub fn get_payout_schedule(
+            &self,
+            proposal_id: ProposalId,
+        ) -> Option<PayoutSchedule> {
+            self.payout_schedules.get(proposal_id)
+        }
+
+        /// Proposals currently pending or active.
+        #[ink(message)]
+        pub fn open_proposals(&self) -> Vec<ProposalId> {
+            let mut open_proposals = self.open_proposals.clone();
+            open_proposals.retain(|proposal_id| {
+                matches!(
+                    self.get_state(*proposal_id),
+                    Ok(ProposalState::Pending | ProposalState::Active)
+                )
+            });
+            open_proposals
+        }
+
+        #[ink(message)]
+        pub fn max_open_proposals(&self) -> u32 {
+            self.max_open_proposals
+        }
+
+        #[ink(message)]
+        pub fn get_proposer_activity(&self, proposer: AccountId) -> ProposerActivity {
+            self.proposer_activity.get(proposer).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_milestone_grant(
+            &self,
+            proposal_id: ProposalId,
+        ) -> Option<MilestoneProgress> {
+            self.milestone_grants.get(proposal_id)
+        }
+
+        /// Replaces the governor's code, leaving `migrate` to be run on the new code.
+        /// Only callable by the governor itself, from a `GenericCall` proposal.
+        #[ink(message)]
+        pub fn upgrade_code(&mut self, code_hash: Hash) -> Result<(), GovernorError> {
+            self.ensure_self()?;
+            self.upgrade(&code_hash)
+        }
+
+        fn upgrade(&mut self, code_hash: &Hash) -> Result<(), GovernorError> {
+            self.migration_pending = true;
+            self.set_code(code_hash)
+        }
+
+        /// Completes an upgrade approved by governance. Runs once on the new code,
+        /// right after the code hash has been replaced.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<(), GovernorError> {
+            if !self.migration_pending {
+                return Err(GovernorError::NoMigrationPending)
+            }
+            if self.version > STORAGE_VERSION {
+                return Err(GovernorError::UnsupportedVersion)
+            }
+            self.migration_pending = false;
+            while self.version < STORAGE_VERSION {
+                self.migrate_from(self.version);
+                self.version += 1;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            self.version
+        }
+
+        #[ink(message)]
+        pub fn get_proposal_vote(&self, proposal_id: ProposalId) -> Option<ProposalVote> {
+            self.proposal_votes.get(proposal_id)
+        }
+
+        #[ink(message)]
+        pub fn get_proposal(&self, proposal_id: ProposalId) -> Option<Proposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        /// Lifecycle state of a proposal, derived from its timestamps, tally and flags.
+        #[ink(message)]
+        pub fn get_state(
+            &self,
+            proposal_id: ProposalId,
+        ) -> Result<ProposalState, GovernorError> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if proposal.canceled {
+                return Ok(ProposalState::Canceled)
+            }
+            if proposal.executed {
+                return Ok(ProposalState::Executed)
+            }
+
+            let now = self.now();
+            if now < proposal.vote_start {
+                return Ok(ProposalState::Pending)
+            }
+            if now <= proposal.vote_end {
+                return Ok(ProposalState::Active)
+            }
+
+            let proposal_vote = self.proposal_votes.get(proposal_id).unwrap_or_default();
+            if !self.quorum_reached(&proposal, &proposal_vote)?
+                || !Self::vote_succeeded(&proposal_vote)
+                || !self.approval_threshold_met(&proposal, &proposal_vote)
+            {
+                return Ok(ProposalState::Defeated)
+            }
+            if now > proposal.expires_at {
+                return Ok(ProposalState::Expired)
+            }
+            if proposal.eta.is_some() {
+                return Ok(ProposalState::Queued)
+            }
+            Ok(ProposalState::Succeeded)
+        }
+
+        #[ink(message)]
+        pub fn has_voted(&self, proposal_id: ProposalId, account_id: AccountId) -> bool {
+            self.receipts.contains((proposal_id, account_id))
+        }
+
+        /// How `account`'s power was counted on the proposal, if at all.
+        #[ink(message)]
+        pub fn get_receipt(
+            &self,
+            proposal_id: ProposalId,
+            account: AccountId,
+        ) -> Option<Receipt> {
+            self.receipts.get((proposal_id, account))
+        }
+
+        /// Weight a holder of `balance` tokens would cast in `vote`, in raw token
+        /// units.
+        ///
+        /// A well-behaved token never reports a balance above its total supply; if it
+        /// does, the weight is clamped to the supply so a single ballot cannot exceed
+        /// it. Without a readable supply no weight is granted.
+        #[ink(message)]
+        pub fn weight_for_balance(&self, balance: Balance) -> Balance {
+            self.total_supply()
+                .map_or(0, |total_supply| balance.min(total_supply))
+        }
+
+        /// Strategy contract new proposals count votes with, if any.
+        #[ink(message)]
+        pub fn voting_strategy(&self) -> Option<AccountId> {
+            self.voting_strategy
+        }
+
+        /// Adapter `Stake` proposals stake with, if any.
+        #[ink(message)]
+        pub fn staking_adapter(&self) -> Option<AccountId> {
+            self.staking_adapter
+        }
+
+        /// Native funds staked with the adapter and the rewards unstaking has
+        /// returned so far.
+        #[ink(message)]
+        pub fn staking(&self) -> (Balance, Balance) {
+            (self.staked, self.staking_rewards)
+        }
+
+        /// Worth of the treasury in the price oracle's reference unit: native funds,
+        /// unlocked governance tokens and registered assets. `None` without an
+        /// oracle or if it cannot price one of them.
+        #[ink(message)]
+        pub fn total_aum(&self) -> Option<Balance> {
+            let governance_tokens =
+                self.treasury_balance_of(self.governance_token).ok()?;
+            let mut aum = self
+                .value_of(None, self.treasury_balance())?
+                .saturating_add(
+                    self.value_of(Some(self.governance_token), governance_tokens)?,
+                );
+            for asset in &self.assets {
+                let balance = self
+                    .asset_balance_of(*asset, self.env().account_id())
+                    .ok()?;
+                aum = aum.saturating_add(self.value_of(Some(*asset), balance)?);
+            }
+            Some(aum)
+        }
+
+        /// Approves the execution of a proposal as a member of the co-signing
+        /// committee.
+        #[ink(message)]
+        pub fn cosign(&mut self, proposal_id: ProposalId) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let signer = self.env().caller();
+            let is_signer = matches!(
+                &self.co_signing,
+                Some(co_signing) if co_signing.signers.contains(&signer)
+            );
+            if !is_signer {
+                return Err(GovernorError::NotSigner)
+            }
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if proposal.executed {
+                return Err(GovernorError::ProposalAlreadyExecuted)
+            }
+            if proposal.canceled {
+                return Err(GovernorError::ProposalCanceled)
+            }
+            if self.signatures.contains((proposal_id, signer)) {
+                return Err(GovernorError::AlreadySigned)
+            }
+            self.signatures.insert((proposal_id, signer), &());
+            Ok(())
+        }
+
+        /// Approves fast-tracking a pending or active proposal as a council member.
+        /// Once enough members approved, voting closes `voting_period` minutes
+        /// after it opened, or now if that has passed. Only proposals paying out up
+        /// to the council's `max_amount`, and no large payouts, qualify.
+        #[ink(message)]
+        pub fn fast_track(
+            &mut self,
+            proposal_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_not_entered()?;
+            let member = self.env().caller();
+            let council = match &self.council {
+                Some(council) if council.members.contains(&member) => council.clone(),
+                _ => return Err(GovernorError::NotCouncilMember),
+            };
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if !matches!(
+                self.get_state(proposal_id)?,
+                ProposalState::Pending | ProposalState::Active
+            ) {
+                return Err(GovernorError::VotePeriodEnded)
+            }
+            let small = matches!(
+                self.payout_value(proposal_id, &proposal.kind),
+                Some(amount) if amount <= council.max_amount
+            );
+            if !small || proposal.large_payout {
+                return Err(GovernorError::ProposalTooLarge)
+            }
+            let mut approvals = self
+                .fast_track_approvals
+                .get(proposal_id)
+                .unwrap_or_default();
+            if approvals.contains(&member) {
+                return Err(GovernorError::AlreadySigned)
+            }
+            approvals.push(member);
+            self.fast_track_approvals.insert(proposal_id, &approvals);
+
+            let approved = approvals
+                .iter()
+                .filter(|approver| council.members.contains(approver))
+                .count();
+            if approved < council.required as usize {
+                return Ok(())
+            }
+            let vote_end =
+                Self::minutes_after(proposal.vote_start, council.voting_period)?
+                    .max(self.now());
+            if vote_end < proposal.vote_end {
+                proposal.vote_end = vote_end;
+                proposal.expires_at =
+                    Self::minutes_after(vote_end, self.config.grace_period)?;
+                self.proposals.insert(proposal_id, &proposal);
+                self.env().emit_event(ProposalFastTracked {
+                    proposal_id,
+                    vote_end,
+                });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn council(&self) -> Option<Council> {
+            self.council.clone()
+        }
+
+        /// Signatures of current committee members on a proposal.
+        #[ink(message)]
+        pub fn signatures(&self, proposal_id: ProposalId) -> u8 {
+            self.signature_count(proposal_id)
+        }
+
+        /// Basis points of each native payout sent to the operations reserve, and
+        /// the reserve.
+        #[ink(message)]
+        pub fn payout_fee(&self) -> (u16, Option<AccountId>) {
+            (self.payout_fee, self.fee_reserve)
+        }
+
+        /// Gas limit of cross-contract calls, zero when they may use all the gas
+        /// left.
+        #[ink(message)]
+        pub fn call_gas_limit(&self) -> u64 {
+            self.call_gas_limit
+        }
+
+        #[ink(message)]
+        pub fn co_signing(&self) -> Option<CoSigning> {
+            self.co_signing.clone()
+        }
+
+        #[ink(message)]
+        pub fn price_oracle(&self) -> Option<AccountId> {
+            self.price_oracle
+        }
+
+        #[ink(message)]
+        pub fn large_payout(&self) -> Option<LargePayout> {
+            self.large_payout
+        }
+
+        #[ink(message)]
+        pub fn emergency_terms(&self) -> EmergencyTerms {
+            self.emergency
+        }
+
+        /// Router `Swap` proposals trade on, if any.
+        #[ink(message)]
+        pub fn dex_router(&self) -> Option<AccountId> {
+            self.dex_router
+        }
+
+        /// Tokens new proposals sum with the governance token balance.
+        #[ink(message)]
+        pub fn weight_sources(&self) -> Vec<WeightSource> {
+            self.weight_sources.clone()
+        }
+
+        /// Quorum in basis points the proposal currently needs. With a quorum decay
+        /// set it falls linearly from `quorum` to the floor while voting is open,
+        /// and stays at its value from the end of voting afterwards.
+        #[ink(message)]
+        pub fn current_quorum(&self, proposal_id: ProposalId) -> Option<u16> {
+            let proposal = self.proposals.get(proposal_id)?;
+            Some(self.quorum_at(&proposal))
+        }
+
+        /// Percentage of the For and Against weight a proposal needs to pass.
+        #[ink(message)]
+        pub fn approval_threshold(&self) -> u8 {
+            self.config.approval_threshold
+        }
+
+        #[ink(message)]
+        pub fn get_config(&self) -> GovernanceConfig {
+            self.config
+        }
+
+        /// Replaces the governance config, emitting `ParameterChanged` for every
+        /// value that changes. Only callable by the governor itself, from a
+        /// `GenericCall` proposal.
+        #[ink(message)]
+        pub fn update_config(
+            &mut self,
+            config: GovernanceConfig,
+        ) -> Result<(), GovernorError> {
+            self.ensure_self()?;
+            self.apply_config(config)
+        }
+
+        fn apply_config(
+            &mut self,
+            config: GovernanceConfig,
+        ) -> Result<(), GovernorError> {
+            if config.min_duration > config.max_duration {
+                return Err(GovernorError::InvalidParameter)
+            }
+            let current = self.config;
+            let params: Vec<GovernorParam> = [
+                (
+                    current.quorum != config.quorum,
+                    GovernorParam::Quorum(config.quorum),
+                ),
+                (
+                    current.quorum_floor != config.quorum_floor,
+                    GovernorParam::QuorumFloor(config.quorum_floor),
+                ),
+                (
+                    current.quorum_decay != config.quorum_decay,
+                    GovernorParam::QuorumDecay(config.quorum_decay),
+                ),
+                (
+                    current.proposal_threshold != config.proposal_threshold,
+                    GovernorParam::ProposalThreshold(config.proposal_threshold),
+                ),
+                (
+                    current.approval_threshold != config.approval_threshold,
+                    GovernorParam::ApprovalThreshold(config.approval_threshold),
+                ),
+                (
+                    current.voting_delay != config.voting_delay,
+                    GovernorParam::VotingDelay(config.voting_delay),
+                ),
+                (
+                    current.min_duration != config.min_duration,
+                    GovernorParam::MinDuration(config.min_duration),
+                ),
+                (
+                    current.max_duration != config.max_duration,
+                    GovernorParam::MaxDuration(config.max_duration),
+                ),
+                (
+                    current.grace_period != config.grace_period,
+                    GovernorParam::GracePeriod(config.grace_period),
+                ),
+                (
+                    current.timelock_delay != config.timelock_delay,
+                    GovernorParam::TimelockDelay(config.timelock_delay),
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(changed, param)| changed.then_some(param))
+            .collect();
+            params.iter().try_for_each(Self::validate_param)?;
+            params.iter().for_each(|param| self.set_param(param));
+            Ok(())
+        }
+
+        /// Changes a single parameter, like a `ParamChange` proposal. Only
+        /// callable by the governor itself, from a `GenericCall` proposal.
+        #[ink(message)]
+        pub fn update_param(
+            &mut self,
+            param: GovernorParam,
+        ) -> Result<(), GovernorError> {
+            self.ensure_self()?;
+            self.apply_param(&param)
+        }
+
+        fn apply_param(&mut self, param: &GovernorParam) -> Result<(), GovernorError> {
+            Self::validate_param(param)?;
+            self.set_param(param);
+            if self.config.min_duration > self.config.max_duration {
+                return Err(GovernorError::InvalidParameter)
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn governance_token(&self) -> AccountId {
+            self.governance_token
+        }
+
+        /// Moves the DAO to a new governance token. Only callable by the governor
+        /// itself, from a `GenericCall` proposal, once no other proposal is open and
+        /// nothing is locked, raised or reserved in the current token.
+        #[ink(message)]
+        pub fn set_governance_token(
+            &mut self,
+            governance_token: AccountId,
+        ) -> Result<(), GovernorError> {
+            self.ensure_self()?;
+            self.replace_governance_token(governance_token)
+        }
+
+        fn replace_governance_token(
+            &mut self,
+            governance_token: AccountId,
+        ) -> Result<(), GovernorError> {
+            if governance_token == self.governance_token {
+                return Err(GovernorError::InvalidParameter)
+            }
+            if !self.unsettled_proposals().is_empty()
+                || self.locked_tokens > 0
+                || self.raising > 0
+                || self.reserved_dividends > 0
+            {
+                return Err(GovernorError::GovernanceTokenInUse)
+            }
+
+            let previous_token = self.governance_token;
+            self.governance_token = governance_token;
+            self.env().emit_event(GovernanceTokenChanged {
+                previous_token,
+                governance_token,
+            });
+            Ok(())
+        }
+
+        /// Applies a `GenericCall` proposal aimed at the governor itself in place.
+        /// A reentrant call would have its writes to the storage struct
+        /// overwritten by `execute` once it returns, so the messages reserved to
+        /// the governor are decoded and run here instead.
+        fn apply_self_call(&mut self, call: &ContractCall) -> Result<(), GovernorError> {
+            fn decode<T: scale::Decode>(input: &[u8]) -> Result<T, GovernorError> {
+                scale::DecodeAll::decode_all(&mut &input[..])
+                    .map_err(|_| GovernorError::InvalidParameter)
+            }
+
+            const UPDATE_CONFIG: [u8; 4] = ink::selector_bytes!("update_config");
+            const UPDATE_PARAM: [u8; 4] = ink::selector_bytes!("update_param");
+            const SET_GOVERNANCE_TOKEN: [u8; 4] =
+                ink::selector_bytes!("set_governance_token");
+            const UPGRADE_CODE: [u8; 4] = ink::selector_bytes!("upgrade_code");
+            const ADD_MEMBER: [u8; 4] = ink::selector_bytes!("add_member");
+            const REMOVE_MEMBER: [u8; 4] = ink::selector_bytes!("remove_member");
+            const DISARM_KILL_SWITCH: [u8; 4] =
+                ink::selector_bytes!("disarm_kill_switch");
+
+            if call.transferred_value > 0 {
+                return Err(GovernorError::InvalidParameter)
+            }
+            let input = &call.input[..];
+            match call.selector {
+                UPDATE_CONFIG => self.apply_config(decode(input)?),
+                UPDATE_PARAM => self.apply_param(&decode(input)?),
+                SET_GOVERNANCE_TOKEN => self.replace_governance_token(decode(input)?),
+                UPGRADE_CODE => self.upgrade(&decode(input)?),
+                ADD_MEMBER => {
+                    self.admit_member(decode(input)?);
+                    Ok(())
+                }
+                REMOVE_MEMBER => {
+                    self.expel_member(decode(input)?);
+                    Ok(())
+                }
+                DISARM_KILL_SWITCH => {
+                    decode::<()>(input)?;
+                    self.disarm(self.env().account_id())
+                }
+                _ => Err(GovernorError::InvalidProposalKind),
+            }
+        }
+
+        /// Only the governor itself, executing a proposal, passes.
+        fn ensure_self(&self) -> Result<(), GovernorError> {
+            if self.env().caller() != self.env().account_id() {
+                return Err(GovernorError::Unauthorized)
+            }
+            Ok(())
+        }
+
+        fn ensure_self_or_admin(&self, account: AccountId) -> Result<(), GovernorError> {
+            if account != self.admin {
+                self.ensure_self()?;
+            }
+            Ok(())
+        }
+
+        pub fn next_proposal_id(&self) -> ProposalId {
+            self.next_proposal_id
+        }
+
+        // used for test
+        #[ink(message)]
+        pub fn now(&self) -> u64 {
+            self.env().block_timestamp()
+        }
+
+        /// Loads a proposal the caller may still amend: their own, not canceled and
+        /// not yet open for voting.
+        fn amendable_proposal(
+            &self,
+            proposal_id: ProposalId,
+        ) -> Result<Proposal, GovernorError> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if proposal.proposer != self.env().caller() {
+                return Err(GovernorError::OnlyProposer)
+            }
+            if proposal.canceled {
+                return Err(GovernorError::ProposalCanceled)
+            }
+            if self.now() >= proposal.vote_start {
+                return Err(GovernorError::VotingAlreadyStarted)
+            }
+            Ok(proposal)
+        }
+
+        /// Checks that voting on the proposal is over, that it passed and that it
+        /// is still within its execution window.
+        /// Enforces the proposal cooldown and the active proposal limit, dropping
+        /// proposals that are no longer pending or active from the activity.
+        fn check_proposer_activity(
+            &self,
+            proposer: AccountId,
+        ) -> Result<ProposerActivity, GovernorError> {
+            let mut activity = self.get_proposer_activity(proposer);
+            if let Some(last_proposed_at) = activity.last_proposed_at {
+                if self.now()
+                    < Self::minutes_after(last_proposed_at, self.proposal_cooldown)?
+                {
+                    return Err(GovernorError::ProposalCooldownActive)
+                }
+            }
+
+            activity.open_proposals.retain(|proposal_id| {
+                matches!(
+                    self.get_state(*proposal_id),
+                    Ok(ProposalState::Pending | ProposalState::Active)
+                )
+            });
+            if self.max_active_proposals > 0
+                && activity.open_proposals.len() >= self.max_active_proposals as usize
+            {
+                return Err(GovernorError::TooManyActiveProposals)
+            }
+            Ok(activity)
+        }
+
+        /// Enforces the limit on proposals open at once, dropping those no longer
+        /// pending or active.
+        fn check_open_proposals(&self) -> Result<Vec<ProposalId>, GovernorError> {
+            let open = self.open_proposals().len();
+            if self.max_open_proposals > 0 && open >= self.max_open_proposals as usize {
+                return Err(GovernorError::TooManyActiveProposals)
+            }
+            Ok(self.unsettled_proposals())
+        }
+
+        /// Tracked proposals that are pending, active, succeeded or queued.
+        fn unsettled_proposals(&self) -> Vec<ProposalId> {
+            let mut unsettled = self.open_proposals.clone();
+            unsettled.retain(|proposal_id| {
+                matches!(
+                    self.get_state(*proposal_id),
+                    Ok(ProposalState::Pending
+                        | ProposalState::Active
+                        | ProposalState::Succeeded
+                        | ProposalState::Queued)
+                )
+            });
+            unsettled
+        }
+
+        /// Keeps treasury payouts of the governance token away from conviction locks.
+        fn ensure_unlocked_tokens(
+            &self,
+            transfer: &TokenTransfer,
+        ) -> Result<(), GovernorError> {
+            if transfer.asset != self.governance_token || self.locked_tokens == 0 {
+                return Ok(())
+            }
+            let balance = self.balance_of(self.env().account_id())?;
+            if balance.saturating_sub(self.locked_tokens) < transfer.amount {
+                return Err(GovernorError::InsufficientTokenBalance)
+            }
+            Ok(())
+        }
+
+        fn ensure_treasurer(&self) -> Result<(), GovernorError> {
+            let caller = self.env().caller();
+            if caller != self.admin && !self.has_role(Role::Treasurer, caller) {
+                return Err(GovernorError::Unauthorized)
+            }
+            Ok(())
+        }
+
+        fn ensure_not_entered(&self) -> Result<(), GovernorError> {
+            if self.entered.get().unwrap_or(false) {
+                return Err(GovernorError::ReentrantCall)
+            }
+            Ok(())
+        }
+
+        fn ensure_not_paused(&self) -> Result<(), GovernorError> {
+            if self.paused {
+                return Err(GovernorError::Paused)
+            }
+            Ok(())
+        }
+
+        /// Proposal that is accepting ballots right now.
+        fn open_proposal(
+            &self,
+            proposal_id: ProposalId,
+        ) -> Result<Proposal, GovernorError> {
+            self.ensure_not_entered()?;
+            self.ensure_not_paused()?;
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(GovernorError::ProposalNotFound)?;
+            if proposal.executed {
+                return Err(GovernorError::ProposalAlreadyExecuted)
+            }
+            if proposal.canceled {
+                return Err(GovernorError::ProposalCanceled)
+            }
+            let now = self.now();
+            if now < proposal.vote_start {
+                return Err(GovernorError::VoteNotStarted)
+            }
+            if now > proposal.vote_end {
+                return Err(GovernorError::VotePeriodEnded)
+            }
+            Ok(proposal)
+        }
+
+        /// Takes `ballot` out of the tally and frees the voter and the delegators
+        /// counted with it to vote again.
+        fn remove_ballot(
+            &mut self,
+            proposal_id: ProposalId,
+            voter: AccountId,
+            ballot: Ballot,
+            proposal_vote: &mut ProposalVote,
+        ) -> Result<(), GovernorError> {
+            proposal_vote.remove(ballot.vote, ballot.weight)?;
+            self.ballots.remove((proposal_id, voter));
+            self.receipts.remove((proposal_id, voter));
+            for delegator in ballot.delegators {
+                self.receipts.remove((proposal_id, delegator));
+            }
+            Ok(())
+        }
+
+        /// Sets `amount` of the treasury aside for the holders of the governance
+        /// token at the snapshot of `proposal`, so tokens acquired once the
+        /// distribution was proposed do not dilute them.
+        fn distribute(
+            &mut self,
+            proposal_id: ProposalId,
+            proposal: &Proposal,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            if self.treasury_balance() < amount {
+                return Err(GovernorError::InsufficientFunds)
+            }
+            let snapshot = proposal.snapshot();
+            let total_supply = self.total_supply_at(snapshot)?;
+            if total_supply == 0 {
+                return Err(GovernorError::InsufficientTokenBalance)
+            }
+
+            let distribution_id = self.next_distribution_id;
+            self.next_distribution_id = distribution_id
+                .checked_add(1)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            let distribution = Distribution {
+                proposal_id,
+                amount,
+                total_supply,
+                snapshot,
+            };
+            self.distributions.insert(distribution_id, &distribution);
+            self.reserved_dividends = self
+                .reserved_dividends
+                .checked_add(amount)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Share of `distribution` owed to `account`, claimed or not.
+        fn dividend_of(
+            &self,
+            account: AccountId,
+            distribution: &Distribution,
+        ) -> Result<Balance, GovernorError> {
+            let balance = self
+                .balance_of_at(account, distribution.snapshot)?
+                .min(distribution.total_supply);
+            Self::mul_div(distribution.amount, balance, distribution.total_supply)
+        }
+
+        /// Counts, or with `voted` false uncounts, a vote of `voter` towards the
+        /// participation of the proposal's epoch.
+        fn record_participation(
+            &mut self,
+            proposal: &Proposal,
+            voter: AccountId,
+            voted: bool,
+        ) {
+            if self.reward_epoch == 0 {
+                return
+            }
+            let epoch = proposal.vote_start / (self.reward_epoch * ONE_MINUTE);
+            let count = self.participation.get((epoch, voter)).unwrap_or(0);
+            let total = self.epoch_participation.get(epoch).unwrap_or(0);
+            let (count, total) = if voted {
+                (count + 1, total + 1)
+            } else {
+                (count.saturating_sub(1), total.saturating_sub(1))
+            };
+            self.participation.insert((epoch, voter), &count);
+            self.epoch_participation.insert(epoch, &total);
+        }
+
+        /// Whether no vote can count towards `epoch` anymore, because every
+        /// proposal started in it has closed.
+        fn epoch_settled(&self, epoch: u64) -> Result<bool, GovernorError> {
+            if self.reward_epoch == 0 {
+                return Ok(false)
+            }
+            let epoch_end = epoch
+                .checked_add(1)
+                .and_then(|next| next.checked_mul(self.reward_epoch))
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            let settled_at = Self::minutes_after(0, epoch_end)?;
+            Ok(self.now() > Self::minutes_after(settled_at, self.config.max_duration)?)
+        }
+
+        /// Stores a receipt for `voter` and each counted delegator, and lists them
+        /// as voters of the proposal.
+        fn record_receipts(
+            &mut self,
+            proposal_id: ProposalId,
+            vote: VoteType,
+            voter: AccountId,
+            own_weight: Balance,
+            delegated: &[(AccountId, Balance)],
+        ) {
+            let timestamp = self.now();
+            let mut voters = self.voters.get(proposal_id).unwrap_or_default();
+            for &(account, weight) in
+                core::iter::once(&(voter, own_weight)).chain(delegated)
+            {
+                let receipt = Receipt {
+                    vote,
+                    weight,
+                    timestamp,
+                };
+                self.receipts.insert((proposal_id, account), &receipt);
+                voters.push(account);
+
+                let mut voted = self.unsettled_votes(account);
+                if !voted.contains(&proposal_id) {
+                    voted.push(proposal_id);
+                }
+                self.voted_proposals.insert(account, &voted);
+            }
+            self.voters.insert(proposal_id, &voters);
+        }
+
+        /// Proposals `account` has a receipt on whose outcome is not final yet.
+        fn unsettled_votes(&self, account: AccountId) -> Vec<ProposalId> {
+            let mut voted = self.voted_proposals.get(account).unwrap_or_default();
+            voted.retain(|&proposal_id| {
+                self.receipts.contains((proposal_id, account))
+                    && matches!(
+                        self.get_state(proposal_id),
+                        Ok(ProposalState::Pending
+                            | ProposalState::Active
+                            | ProposalState::Succeeded
+                            | ProposalState::Queued)
+                    )
+            });
+            voted
+        }
+
+        /// Clears the delegation of `delegator`, returning its former delegate.
+        fn remove_delegation(&mut self, delegator: AccountId) -> Option<AccountId> {
+            let delegate = self.delegates.take(delegator)?;
+            let mut delegators = self.delegators.get(delegate).unwrap_or_default();
+            delegators.retain(|account| *account != delegator);
+            self.delegators.insert(delegate, &delegators);
+            Some(delegate)
+        }
+
+        fn ensure_succeeded(
+            &self,
+            proposal_id: ProposalId,
+            proposal: &Proposal,
+        ) -> Result<(), GovernorError> {
+            if proposal.executed {
+                return Err(GovernorError::ProposalAlreadyExecuted)
+            }
+            if proposal.canceled {
+                return Err(GovernorError::ProposalCanceled)
+            }
+
+            let proposal_vote = self.proposal_votes.get(proposal_id).unwrap_or_default();
+            if !self.quorum_reached(proposal, &proposal_vote)? {
+                return Err(GovernorError::QuorumNotReached)
+            }
+            let now = self.now();
+            if now <= proposal.vote_end {
+                return Err(GovernorError::VotePeriodNotEnded)
+            }
+            if !Self::vote_succeeded(&proposal_vote) {
+                return Err(GovernorError::ProposalNotAccepted)
+            }
+            if !self.approval_threshold_met(proposal, &proposal_vote) {
+                return Err(GovernorError::ApprovalThresholdNotMet)
+            }
+            if now > proposal.expires_at {
+                return Err(GovernorError::ProposalExpired)
+            }
+            Ok(())
+        }
+
+        /// Weight `account` casts on `proposal`: its governance token balance as of
+        /// the proposal snapshot, so tokens moved once voting opens cannot vote twice.
+        fn voting_power(
+            &self,
+            account: AccountId,
+            proposal: &Proposal,
+        ) -> Result<Balance, GovernorError> {
+            if let Some(strategy) = proposal.voting_strategy {
+                return self.strategy_weight(strategy, account, proposal.snapshot())
+            }
+            if let CountingMode::Membership(collection) = proposal.counting_mode {
+                return Ok(proposal
+                    .counting_mode
+                    .weight(self.nft_balance_of(collection, account)?.into()))
+            }
+            if matches!(proposal.counting_mode, CountingMode::VoteEscrow) {
+                return Ok(match self.vote_locks.get(account) {
+                    Some(vote_lock) if vote_lock.updated_at <= proposal.snapshot() => {
+                        vote_lock.weight_at(proposal.vote_start)
+                    }
+                    _ => 0,
+                })
+            }
+            let balance = self
+                .balance_of_at(account, proposal.snapshot())?
+                .min(self.total_supply_at(proposal.snapshot())?);
+            let mut sourced: Balance = 0;
+            for source in &proposal.weight_sources {
+                let balance = self
+                    .asset_balance_of_at(source.token, account, proposal.snapshot())?
+                    .min(self.asset_total_supply_at(source.token, proposal.snapshot())?);
+                sourced += balance.saturating_mul(source.multiplier.into()) / 100;
+            }
+            Ok(proposal
+                .counting_mode
+                .weight(balance.saturating_add(sourced)))
+        }
+
+        /// Weight the whole supply would cast on `proposal`. Under membership
+        /// counting that is the number of NFTs, assuming one per member.
+        fn total_weight(&self, proposal: &Proposal) -> Result<Balance, GovernorError> {
+            match (proposal.voting_strategy, proposal.counting_mode) {
+                (Some(strategy), _) => {
+                    self.strategy_total_weight(strategy, proposal.snapshot())
+                }
+                (None, CountingMode::Membership(collection)) => {
+                    self.nft_total_supply(collection)
+                }
+                (None, CountingMode::VoteEscrow) => {
+                    self.total_supply_at(proposal.snapshot())
+                }
+                (None, mode) => {
+                    let mut sourced: Balance = 0;
+                    for source in &proposal.weight_sources {
+                        sourced += self
+                            .asset_total_supply_at(source.token, proposal.snapshot())?
+                            .saturating_mul(source.multiplier.into())
+                            / 100;
+                    }
+                    Ok(mode.weight(
+                        self.total_supply_at(proposal.snapshot())?
+                            .saturating_add(sourced),
+                    ))
+                }
+            }
+        }
+
+        /// Most weight a single account may add to a tally on `proposal`.
+        fn weight_cap(&self, proposal: &Proposal) -> Result<Balance, GovernorError> {
+            if self.max_vote_weight == 0 {
+                return Ok(Balance::MAX)
+            }
+            Ok(self
+                .total_weight(proposal)?
+                .saturating_mul(self.max_vote_weight as Balance)
+                / 100)
+        }
+
+        /// The current quorum is in basis points of the governance token supply at
+        /// the snapshot, weighted like a single ballot under the proposal's counting
+        /// mode.
+        fn quorum_reached(
+            &self,
+            proposal: &Proposal,
+            proposal_vote: &ProposalVote,
+        ) -> Result<bool, GovernorError> {
+            let total_supply = self.total_weight(proposal)?;
+            if total_supply == 0 {
+                return Ok(false)
+            }
+            let votes = proposal_vote
+                .for_votes
+                .saturating_add(proposal_vote.against_votes)
+                .saturating_add(proposal_vote.abstain_votes);
+            Ok(votes.saturating_mul(BASIS_POINTS as Balance)
+                >= total_supply.saturating_mul(self.quorum_at(proposal) as Balance))
+        }
+
+        /// Quorum in basis points, decayed for the time `proposal` has been open.
+        /// Large payouts never go below the large payout quorum.
+        fn quorum_at(&self, proposal: &Proposal) -> u16 {
+            let quorum = match proposal.track {
+                Some(track) => track.quorum,
+                None => self.decayed_quorum(proposal),
+            };
+            match proposal.kind {
+                ProposalKind::EmergencyWithdrawal(_) => {
+                    return quorum.max(self.emergency.quorum)
+                }
+                ProposalKind::Sunset => return quorum.max(SUNSET_QUORUM),
+                _ => {}
+            }
+            match self.large_payout {
+                Some(large_payout) if proposal.large_payout => {
+                    quorum.max(large_payout.quorum)
+                }
+                _ => quorum,
+            }
+        }
+
+        fn decayed_quorum(&self, proposal: &Proposal) -> u16 {
+            if self.config.quorum_decay == 0
+                || self.config.quorum_floor >= self.config.quorum
+            {
+                return self.config.quorum
+            }
+            let open_for = self
+                .now()
+                .min(proposal.vote_end)
+                .saturating_sub(proposal.vote_start);
+            let decay_time = self.config.quorum_decay * ONE_MINUTE;
+            if open_for >= decay_time {
+                return self.config.quorum_floor
+            }
+            let decayed = (self.config.quorum - self.config.quorum_floor) as u64
+                * open_for
+                / decay_time;
+            self.config.quorum - decayed as u16
+        }
+
+        fn vote_succeeded(proposal_vote: &ProposalVote) -> bool {
+            proposal_vote.for_votes >= proposal_vote.against_votes
+        }
+
+        /// Abstentions are not part of the cast votes the threshold applies to.
+        fn approval_threshold_met(
+            &self,
+            proposal: &Proposal,
+            proposal_vote: &ProposalVote,
+        ) -> bool {
+            let base = match proposal.track {
+                Some(track) => track.approval_threshold,
+                None => self.config.approval_threshold,
+            };
+            let approval_threshold = match self.large_payout {
+                _ if matches!(proposal.kind, ProposalKind::EmergencyWithdrawal(_)) => {
+                    base.max(self.emergency.approval_threshold)
+                }
+                _ if matches!(proposal.kind, ProposalKind::Sunset) => {
+                    base.max(SUNSET_APPROVAL_THRESHOLD)
+                }
+                Some(large_payout) if proposal.large_payout => {
+                    base.max(large_payout.approval_threshold)
+                }
+                _ => base,
+            };
+            let cast_votes = proposal_vote
+                .for_votes
+                .saturating_add(proposal_vote.against_votes);
+            proposal_vote.for_votes.saturating_mul(100)
+                >= cast_votes.saturating_mul(approval_threshold as Balance)
+        }
+
+        /// Whether the proposal needs more committee signatures to execute.
+        fn signatures_missing(
+            &self,
+            proposal_id: ProposalId,
+            kind: &ProposalKind,
+        ) -> bool {
+            let Some(co_signing) = &self.co_signing else {
+                return false
+            };
+            let needed = !matches!(
+                self.payout_value(proposal_id, kind),
+                Some(value) if value <= co_signing.threshold
+            );
+            needed && self.signature_count(proposal_id) < co_signing.required
+        }
+
+        fn signature_count(&self, proposal_id: ProposalId) -> u8 {
+            self.co_signing.as_ref().map_or(0, |co_signing| {
+                co_signing
+                    .signers
+                    .iter()
+                    .filter(|signer| self.signatures.contains((proposal_id, **signer)))
+                    .count() as u8
+            })
+        }
+
+        /// Payouts the oracle cannot value count as large.
+        fn is_large_payout(&self, proposal_id: ProposalId, kind: &ProposalKind) -> bool {
+            let Some(large_payout) = self.large_payout else {
+                return false
+            };
+            let Some(amount) = self.payout_value(proposal_id, kind) else {
+                return true
+            };
+            match large_payout.threshold {
+                PayoutThreshold::Absolute(threshold) => amount > threshold,
+                PayoutThreshold::Treasury(basis_points) => {
+                    let treasury = match self.price_oracle {
+                        Some(_) => self.total_aum(),
+                        None => Some(self.treasury_balance()),
+                    };
+                    let Some(treasury) = treasury else {
+                        return true
+                    };
+                    amount.saturating_mul(BASIS_POINTS as Balance)
+                        > treasury.saturating_mul(basis_points as Balance)
+                }
+            }
+        }
+
+        fn ensure_within_track(
+            &self,
+            proposal_id: ProposalId,
+            kind: &ProposalKind,
+            track: Option<Track>,
+        ) -> Result<(), GovernorError> {
+            let Some(max_spend) = track.and_then(|track| track.max_spend) else {
+                return Ok(())
+            };
+            match self.payout_value(proposal_id, kind) {
+                Some(amount) if amount <= max_spend => Ok(()),
+                _ => Err(GovernorError::ProposalTooLarge),
+            }
+        }
+
+        fn ensure_recipients_allowed(
+            &self,
+            kind: &ProposalKind,
+        ) -> Result<(), GovernorError> {
+            if self
+                .recipients(kind)
+                .iter()
+                .any(|recipient| self.blacklist.contains(recipient))
+            {
+                return Err(GovernorError::RecipientBlacklisted)
+            }
+            Ok(())
+        }
+
+        /// Accounts a proposal pays, directly or through the grant it releases.
+        fn recipients(&self, kind: &ProposalKind) -> Vec<AccountId> {
+            match kind {
+                ProposalKind::NativeTransfer(transfers) => {
+                    transfers.iter().map(|transfer| transfer.to).collect()
+                }
+                ProposalKind::TokenTransfer(transfers) => {
+                    transfers.iter().map(|transfer| transfer.to).collect()
+                }
+                ProposalKind::GenericCall(call) if call.transferred_value > 0 => {
+                    vec![call.callee]
+                }
+                ProposalKind::Recurring(payout) | ProposalKind::Hire(payout) => {
+                    vec![payout.to]
+                }
+                ProposalKind::Milestones(grant) => vec![grant.to],
+                ProposalKind::ReleaseMilestone(grant_id) => {
+                    self.milestone_grants
+                        .get(grant_id)
+                        .map(|progress| progress.grant.to)
+                        .into_iter()
+                        .collect()
+                }
+                ProposalKind::MultiChoice(choice) => {
+                    choice
+                        .options
+                        .iter()
+                        .map(|option| option.payout.to)
+                        .collect()
+                }
+                ProposalKind::Stream(payout) => vec![payout.to],
+                ProposalKind::Dismiss(contributor) => vec![*contributor],
+                ProposalKind::Escrow(transfer) => vec![transfer.to],
+                ProposalKind::ReleaseEscrow(escrow_id) => {
+                    self.escrows
+                        .get(escrow_id)
+                        .map(|escrow| escrow.transfer.to)
+                        .into_iter()
+                        .collect()
+                }
+                ProposalKind::Vesting(grant) => vec![grant.to],
+                ProposalKind::EmergencyWithdrawal(recovery) => vec![*recovery],
+                _ => Vec::new(),
+            }
+        }
+
+        /// Native funds a proposal pays out, plus its PSP22 payouts once a price
+        /// oracle values them.
+        fn payout_value(
+            &self,
+            proposal_id: ProposalId,
+            kind: &ProposalKind,
+        ) -> Option<Balance> {
+            let native = self.native_outflow(proposal_id, kind);
+            if self.price_oracle.is_none() {
+                return Some(native)
+            }
+            let mut value = self.value_of(None, native)?;
+            let tokens: Vec<(AccountId, Balance)> = match kind {
+                ProposalKind::TokenTransfer(transfers) => {
+                    transfers
+                        .iter()
+                        .map(|transfer| (transfer.asset, transfer.amount))
+                        .collect()
+                }
+                ProposalKind::Swap(Swap {
+                    asset_in: Some(asset),
+                    amount_in,
+                    ..
+                }) => vec![(*asset, *amount_in)],
+                _ => Vec::new(),
+            };
+            for (asset, amount) in tokens {
+                value = value.saturating_add(self.value_of(Some(asset), amount)?);
+            }
+            Some(value)
+        }
+
+        /// Worth of `amount` of `asset` in the oracle's reference unit.
+        fn value_of(&self, asset: Option<AccountId>, amount: Balance) -> Option<Balance> {
+            if amount == 0 {
+                return Some(0)
+            }
+            let price = self.asset_price(self.price_oracle?, asset)?;
+            Some(amount.saturating_mul(price) / PRICE_SCALE)
+        }
+
+        /// `value * numerator / denominator`, rounded down. The product is kept in
+        /// 256 bits, so large balances and supplies do not overflow it.
+        fn mul_div(
+            value: Balance,
+            numerator: Balance,
+            denominator: Balance,
+        ) -> Result<Balance, GovernorError> {
+            const LOW: u128 = u64::MAX as u128;
+            if denominator == 0 {
+                return Err(GovernorError::ArithmeticOverflow)
+            }
+            let (a_high, a_low) = (value >> 64, value & LOW);
+            let (b_high, b_low) = (numerator >> 64, numerator & LOW);
+            let low_low = a_low * b_low;
+            let low_high = a_low * b_high;
+            let high_low = a_high * b_low;
+            let middle = (low_low >> 64) + (low_high & LOW) + (high_low & LOW);
+            let low = (low_low & LOW) | (middle << 64);
+            let high =
+                a_high * b_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+            if high >= denominator {
+                return Err(GovernorError::ArithmeticOverflow)
+            }
+
+            // Long division of `high:low`, one bit at a time.
+            let mut remainder = high;
+            let mut quotient: Balance = 0;
+            for bit in (0..128).rev() {
+                let carry = remainder >> 127;
+                remainder = (remainder << 1) | ((low >> bit) & 1);
+                quotient <<= 1;
+                if carry == 1 || remainder >= denominator {
+                    remainder = remainder.wrapping_sub(denominator);
+                    quotient |= 1;
+                }
+            }
+            Ok(quotient)
+        }
+
+        /// Timestamp `minutes` after `timestamp`.
+        fn minutes_after(timestamp: u64, minutes: u64) -> Result<u64, GovernorError> {
+            minutes
+                .checked_mul(ONE_MINUTE)
+                .and_then(|duration| timestamp.checked_add(duration))
+                .ok_or(GovernorError::ArithmeticOverflow)
+        }
+
+        fn validate_amount(amount: Balance) -> Result<(), GovernorError> {
+            if amount == 0 {
+                return Err(GovernorError::AmountShouldNotBeZero)
+            }
+            Ok(())
+        }
+
+        fn validate_kind(kind: &ProposalKind) -> Result<(), GovernorError> {
+            match kind {
+                ProposalKind::NativeTransfer(transfers) => {
+                    if transfers.is_empty() {
+                        return Err(GovernorError::EmptyProposal)
+                    }
+                    transfers
+                        .iter()
+                        .try_for_each(|transfer| Self::validate_amount(transfer.amount))
+                }
+                ProposalKind::TokenTransfer(transfers) => {
+                    if transfers.is_empty() {
+                        return Err(GovernorError::EmptyProposal)
+                    }
+                    transfers
+                        .iter()
+                        .try_for_each(|transfer| Self::validate_amount(transfer.amount))
+                }
+                ProposalKind::GenericCall(_) | ProposalKind::Upgrade(_) => Ok(()),
+                ProposalKind::Recurring(payout) | ProposalKind::Hire(payout) => {
+                    Self::validate_amount(payout.amount)?;
+                    if payout.period == 0 || payout.periods == 0 {
+                        return Err(GovernorError::InvalidSchedule)
+                    }
+                    Ok(())
+                }
+                ProposalKind::Milestones(grant) => {
+                    if grant.amounts.is_empty() {
+                        return Err(GovernorError::EmptyProposal)
+                    }
+                    grant
+                        .amounts
+                        .iter()
+                        .copied()
+                        .try_for_each(Self::validate_amount)
+                }
+                ProposalKind::ReleaseMilestone(_) => Ok(()),
+                ProposalKind::FundRewards(funding) => {
+                    Self::validate_amount(funding.amount)
+                }
+                ProposalKind::Distribute(amount) => Self::validate_amount(*amount),
+                ProposalKind::Vesting(grant) => {
+                    Self::validate_amount(grant.amount)?;
+                    if grant.duration == 0 || grant.cliff > grant.duration {
+                        return Err(GovernorError::InvalidSchedule)
+                    }
+                    Ok(())
+                }
+                ProposalKind::OpenRound(terms) => {
+                    Self::validate_amount(terms.hard_cap)?;
+                    if terms.soft_cap > terms.hard_cap
+                        || terms.rate == 0
+                        || terms.duration == 0
+                    {
+                        return Err(GovernorError::InvalidParameter)
+                    }
+                    Ok(())
+                }
+                ProposalKind::Stream(payout) => {
+                    Self::validate_amount(payout.amount)?;
+                    if payout.duration == 0 {
+                        return Err(GovernorError::InvalidSchedule)
+                    }
+                    Ok(())
+                }
+                ProposalKind::CancelStream(_)
+                | ProposalKind::Clawback(_)
+                | ProposalKind::Dismiss(_)
+                | ProposalKind::EmergencyWithdrawal(_)
+                | ProposalKind::Sunset => Ok(()),
+                ProposalKind::Escrow(transfer) => Self::validate_amount(transfer.amount),
+                ProposalKind::ReleaseEscrow(_) => Ok(()),
+                ProposalKind::Stake(amount) | ProposalKind::Unstake(amount) => {
+                    Self::validate_amount(*amount)
+                }
+                ProposalKind::Buyback(buyback) => {
+                    Self::validate_amount(buyback.amount.max(buyback.contributed))
+                }
+                ProposalKind::Swap(swap) => {
+                    Self::validate_amount(swap.amount_in)?;
+                    if swap.asset_in == swap.asset_out {
+                        return Err(GovernorError::InvalidParameter)
+                    }
+                    Ok(())
+                }
+                ProposalKind::MultiChoice(choice) => {
+                    if choice.options.len() < 2 || choice.options.len() > MAX_CHOICES {
+                        return Err(GovernorError::InvalidChoice)
+                    }
+                    choice.options.iter().try_for_each(|option| {
+                        Self::validate_amount(option.payout.amount)
+                    })
+                }
+                ProposalKind::ParamChange(params) => {
+                    if params.is_empty() {
+                        return Err(GovernorError::EmptyProposal)
+                    }
+                    params.iter().try_for_each(Self::validate_param)
+                }
+            }
+        }
+
+        fn release_milestone(
+            &mut self,
+            grant_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            let mut progress = self
+                .milestone_grants
+                .get(grant_id)
+                .ok_or(GovernorError::MilestoneGrantNotFound)?;
+            let amount = *progress
+                .grant
+                .amounts
+                .get(progress.released as usize)
+                .ok_or(GovernorError::AllMilestonesReleased)?;
+
+            progress.released += 1;
+            self.milestone_grants.insert(grant_id, &progress);
+            self.pay(progress.grant.to, amount, Movement::Payout, Some(grant_id))?;
+            self.record_disbursement(grant_id, amount);
+
+            Ok(())
+        }
+
+        /// Periods of `schedule` that have started, the first one at `start`.
+        fn unlocked_periods(
+            &self,
+            schedule: &PayoutSchedule,
+        ) -> Result<u32, GovernorError> {
+            let period = Self::minutes_after(0, schedule.payout.period)?;
+            let elapsed_periods = self
+                .now()
+                .saturating_sub(schedule.start)
+                .checked_div(period)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            Ok(elapsed_periods
+                .saturating_add(1)
+                .min(schedule.payout.periods as u64) as u32)
+        }
+
+        fn reserve_grant(&mut self, amount: Balance) -> Result<(), GovernorError> {
+            self.reserved_grants = self
+                .reserved_grants
+                .checked_add(amount)
+                .ok_or(GovernorError::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        fn cancel_stream(&mut self, stream_id: ProposalId) -> Result<(), GovernorError> {
+            let mut stream = self
+                .streams
+                .get(stream_id)
+                .ok_or(GovernorError::StreamNotFound)?;
+            let now = self.now();
+            if now < stream.end {
+                let vested = stream.vested_at(now);
+                self.reserved_grants -= stream.amount - vested;
+                stream.amount = vested;
+                stream.end = now.max(stream.start);
+                self.streams.insert(stream_id, &stream);
+            }
+            Ok(())
+        }
+
+        /// Cuts a vesting grant to what has vested, dropping it from the
+        /// recipient's grants once nothing is left to claim.
+        fn claw_back_vesting(
+            &mut self,
+            grant_id: ProposalId,
+        ) -> Result<(), GovernorError> {
+            let mut vesting = self
+                .vestings
+                .get(grant_id)
+                .ok_or(GovernorError::GrantNotFound)?;
+            let now = self.now();
+            if now < vesting.end {
+                let vested = vesting.vested_at(now);
+                self.reserved_grants -= vesting.amount - vested;
+                vesting.amount = vested;
+                vesting.end = now.max(vesting.start);
+                vesting.cliff = vesting.cliff.min(vesting.end);
+                self.vestings.insert(grant_id, &vesting);
+            }
+            if vesting.claimed == vesting.amount {
+                let mut grants = self.vesting_grants.get(vesting.to).unwrap_or_default();
+                grants.retain(|id| *id != grant_id);
+                self.vesting_grants.insert(vesting.to, &grants);
+            }
+            Ok(())
+        }
+
+        /// Sends native treasury funds, recording the movement in the ledger.
+        /// Payouts leave the payout fee with the operations reserve.
+        fn pay(
+            &mut self,
+            to: AccountId,
+            amount: Balance,
+            movement: Movement,
+            proposal_id: Option<ProposalId>,
+        ) -> Result<(), GovernorError> {
+            let mut amount = amount;
+            if let (Movement::Payout, Some(reserve)) = (movement, self.fee_reserve) {
+                let fee = amount * self.payout_fee as Balance / BASIS_POINTS as Balance;
+                if fee > 0 {
+                    self.transfer_native(reserve, fee)?;
+                    self.log_movement(
+                        Movement::Fee,
+                        false,
+                        reserve,
+                        None,
+                        fee,
+                        proposal_id,
+                    );
+                    amount -= fee;
+                }
+            }
+            self.transfer_native(to, amount)?;
+            self.log_movement(movement, false, to, None, amount, proposal_id);
+            Ok(())
+        }
+
+        /// Sends everything the treasury may spend to `recovery`.
+        fn sweep_treasury(
+            &mut self,
+            proposal_id: ProposalId,
+            recovery: AccountId,
+        ) -> Result<(), GovernorError> {
+            let mut assets = self.assets.clone();
+            assets.push(self.governance_token);
+            for asset in assets {
+                let amount = self.treasury_balance_of(asset)?;
+                if amount == 0 {
+                    continue
+                }
+                self.log_movement(
+                    Movement::Emergency,
+                    false,
+                    recovery,
+                    Some(asset),
+                    amount,
+                    Some(proposal_id),
+                );
+                self.transfer_token(&TokenTransfer {
+                    asset,
+                    to: recovery,
+                    amount,
+                })?;
+            }
+            let amount = self.treasury_balance();
+            if amount > 0 {
+                self.pay(recovery, amount, Movement::Emergency, Some(proposal_id))?;
+            }
+            Ok(())
+        }
+
+        fn log_movement(
+            &mut self,
+            movement: Movement,
+            inflow: bool,
+            counterparty: AccountId,
+            asset: Option<AccountId>,
+            amount: Balance,
+            proposal_id: Option<ProposalId>,
+        ) {
+            let entry = LedgerEntry {
+                timestamp: self.now(),
+                movement,
+                inflow,
+                counterparty,
+                asset,
+                amount,
+                proposal_id,
+            };
+            self.ledger.insert(self.ledger_len, &entry);
+            self.ledger_len += 1;
+        }
+
+        /// Mints a receipt NFT to the contributor if the amount reaches a tier.
+        fn issue_receipt(
+            &mut self,
+            contributor: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            let Some(receipt_nft) = self.receipt_nft else {
+                return Ok(())
+            };
+            let tier = self
+                .receipt_tiers
+                .iter()
+                .take_while(|threshold| **threshold <= amount)
+                .count() as u8;
+            if tier == 0 {
+                return Ok(())
+            }
+
+            let receipt_id = self.next_receipt_id;
+            self.mint_receipt(receipt_nft, contributor, receipt_id)?;
+            self.next_receipt_id += 1;
+            let receipt = ContributionReceipt {
+                contributor,
+                amount,
+                tier,
+            };
+            self.contribution_receipts.insert(receipt_id, &receipt);
+            Ok(())
+        }
+
+        /// Trades on the DEX router, checking what the treasury actually received
+        /// against the slippage bound. Returns the amount received.
+        fn swap(&mut self, swap: &Swap) -> Result<Balance, GovernorError> {
+            let router = self.dex_router.ok_or(GovernorError::NoDexRouter)?;
+            match swap.asset_in {
+                None if self.treasury_balance() < swap.amount_in => {
+                    return Err(GovernorError::InsufficientFunds)
+                }
+                None => {}
+                Some(asset) => {
+                    self.ensure_unlocked_tokens(&TokenTransfer {
+                        asset,
+                        to: router,
+                        amount: swap.amount_in,
+                    })?;
+                    self.approve_token(asset, router, swap.amount_in)?;
+                }
+            }
+
+            let before = self.holding(swap.asset_out)?;
+            self.call_router(router, swap)?;
+            let received = self.holding(swap.asset_out)?.saturating_sub(before);
+            if received < swap.min_amount_out {
+                return Err(GovernorError::SlippageExceeded)
+            }
+            Ok(received)
+        }
+
+        /// Treasury balance of `asset`, or of native funds for `None`.
+        fn holding(&self, asset: Option<AccountId>) -> Result<Balance, GovernorError> {
+            match asset {
+                Some(asset) => self.asset_balance_of(asset, self.env().account_id()),
+                None => Ok(self.env().balance()),
+            }
+        }
+
+        fn unsettled_escrow(
+            &self,
+            escrow_id: ProposalId,
+        ) -> Result<Escrow, GovernorError> {
+            let escrow = self
+                .escrows
+                .get(escrow_id)
+                .ok_or(GovernorError::EscrowNotFound)?;
+            if escrow.settled {
+                return Err(GovernorError::EscrowSettled)
+            }
+            Ok(escrow)
+        }
+
+        /// Native funds `kind` commits when executed. Recurring payouts and streams
+        /// count in full, milestones tranche by tranche.
+        fn native_outflow(
+            &self,
+            proposal_id: ProposalId,
+            kind: &ProposalKind,
+        ) -> Balance {
+            match kind {
+                ProposalKind::NativeTransfer(transfers) => {
+                    transfers.iter().map(|transfer| transfer.amount).sum()
+                }
+                ProposalKind::Recurring(payout) | ProposalKind::Hire(payout) => {
+                    payout.amount * payout.periods as Balance
+                }
+                ProposalKind::ReleaseMilestone(grant_id) => {
+                    self.milestone_grants
+                        .get(grant_id)
+                        .and_then(|progress| {
+                            progress
+                                .grant
+                                .amounts
+                                .get(progress.released as usize)
+                                .copied()
+                        })
+                        .unwrap_or(0)
+                }
+                ProposalKind::MultiChoice(choice) => {
+                    self.choice_winner(proposal_id)
+                        .and_then(|winner| choice.options.get(winner as usize))
+                        .map_or(0, |option| option.payout.amount)
+                }
+                ProposalKind::FundRewards(funding) => funding.amount,
+                ProposalKind::Distribute(amount) => *amount,
+                ProposalKind::Stream(payout) => payout.amount,
+                ProposalKind::Vesting(grant) => grant.amount,
+                ProposalKind::Escrow(transfer) => transfer.amount,
+                ProposalKind::Stake(amount) => *amount,
+                ProposalKind::Buyback(buyback) => buyback.amount,
+                ProposalKind::Swap(swap) if swap.asset_in.is_none() => swap.amount_in,
+                _ => 0,
+            }
+        }
+
+        /// Budget left in the current epoch, after carrying over what earlier
+        /// epochs left unspent.
+        fn current_budget(&self) -> Balance {
+            let elapsed = self
+                .now()
+                .saturating_div(self.budget_epoch * ONE_MINUTE)
+                .saturating_sub(self.budget_period);
+            if elapsed == 0 {
+                return self.budget_remaining
+            }
+            let accumulated = self
+                .budget_remaining
+                .saturating_add(self.budget_cap.saturating_mul(elapsed as Balance));
+            match self.budget_carry_over {
+                CarryOver::None => self.budget_cap,
+                CarryOver::Full => accumulated,
+                CarryOver::UpTo(max) => {
+                    accumulated.min(self.budget_cap.saturating_add(max))
+                }
+            }
+        }
+
+        fn spend_budget(&mut self, amount: Balance) -> Result<(), GovernorError> {
+            if self.budget_cap == 0 || amount == 0 {
+                return Ok(())
+            }
+            let remaining = self.current_budget();
+            if amount > remaining {
+                return Err(GovernorError::BudgetExceeded)
+            }
+            self.budget_period = self.now() / (self.budget_epoch * ONE_MINUTE);
+            self.budget_remaining = remaining - amount;
+            Ok(())
+        }
+
+        /// Restarts the budget with a full cap in the current epoch.
+        fn reset_budget(&mut self) {
+            self.budget_period = self.now() / (self.budget_epoch * ONE_MINUTE);
+            self.budget_remaining = self.budget_cap;
+        }
+
+        fn record_disbursement(&mut self, proposal_id: ProposalId, amount: Balance) {
+            let mut investment = self.investments.get(proposal_id).unwrap_or_default();
+            investment.disbursed += amount;
+            self.investments.insert(proposal_id, &investment);
+            self.portfolio.disbursed += amount;
+        }
+
+        /// Transforms storage of layout `version` into the next one. Versions that
+        /// change the storage layout add a step here and bump `STORAGE_VERSION`.
+        fn migrate_from(&mut self, version: u32) {
+            if version == 0 {
+                // Unversioned governors had no quorum floor; it starts at the quorum.
+                if self.config.quorum_floor == 0 {
+                    self.config.quorum_floor = self.config.quorum;
+                }
+            }
+        }
+
+        fn validate_param(param: &GovernorParam) -> Result<(), GovernorError> {
+            match param {
+                GovernorParam::Quorum(basis_points)
+                | GovernorParam::QuorumFloor(basis_points)
+                | GovernorParam::PayoutFee(basis_points)
+                    if *basis_points > BASIS_POINTS =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::MaxVoteWeight(percent)
+                | GovernorParam::ApprovalThreshold(percent)
+                    if *percent > 100 =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::MinDuration(0)
+                | GovernorParam::MaxDuration(0)
+                | GovernorParam::BudgetEpoch(0) => Err(GovernorError::InvalidParameter),
+                GovernorParam::Emergency(emergency)
+                    if emergency.approval_threshold > 100
+                        || emergency.quorum > BASIS_POINTS =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::LargePayout(Some(large_payout))
+                    if large_payout.approval_threshold > 100
+                        || large_payout.quorum > BASIS_POINTS
+                        || matches!(
+                            large_payout.threshold,
+                            PayoutThreshold::Treasury(basis_points)
+                                if basis_points > BASIS_POINTS
+                        ) =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::ObjectionThreshold(basis_points)
+                    if *basis_points > BASIS_POINTS =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::ObjectionWindow(minutes)
+                | GovernorParam::KillSwitchDelay(minutes)
+                    if *minutes > MAX_SAFEGUARD_DELAY =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::Track(track)
+                    if track.quorum > BASIS_POINTS
+                        || track.approval_threshold > 100
+                        || track.voting_period == 0 =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::Council(Some(council))
+                    if council.required == 0
+                        || council.required as usize > council.members.len()
+                        || council.members.len() > MAX_COUNCIL_MEMBERS
+                        || council
+                            .members
+                            .iter()
+                            .enumerate()
+                            .any(|(i, member)| council.members[..i].contains(member)) =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::CoSigning(Some(co_signing))
+                    if co_signing.required == 0
+                        || co_signing.required as usize > co_signing.signers.len()
+                        || co_signing.signers.len() > MAX_SIGNERS
+                        || co_signing.signers.iter().enumerate().any(
+                            |(i, signer)| co_signing.signers[..i].contains(signer),
+                        ) =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::WeightSources(sources)
+                    if sources.len() > MAX_WEIGHT_SOURCES =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                GovernorParam::ReceiptTiers(tiers)
+                    if tiers.len() > MAX_RECEIPT_TIERS
+                        || tiers.first() == Some(&0)
+                        || tiers.windows(2).any(|pair| pair[0] >= pair[1]) =>
+                {
+                    Err(GovernorError::InvalidParameter)
+                }
+                _ => Ok(()),
+            }
+        }
+
+        fn set_param(&mut self, param: &GovernorParam) {
+            match *param {
+                GovernorParam::Quorum(quorum) => self.config.quorum = quorum,
+                GovernorParam::VotingDelay(voting_delay) => {
+                    self.config.voting_delay = voting_delay
+                }
+                GovernorParam::MinDuration(min_duration) => {
+                    self.config.min_duration = min_duration
+                }
+                GovernorParam::MaxDuration(max_duration) => {
+                    self.config.max_duration = max_duration
+                }
+                GovernorParam::GracePeriod(grace_period) => {
+                    self.config.grace_period = grace_period
+                }
+                GovernorParam::TimelockDelay(timelock_delay) => {
+                    self.config.timelock_delay = timelock_delay
+                }
+                GovernorParam::ProposalBond(proposal_bond) => {
+                    self.proposal_bond = proposal_bond
+                }
+                GovernorParam::ProposalThreshold(proposal_threshold) => {
+                    self.config.proposal_threshold = proposal_threshold
+                }
+                GovernorParam::ProposalCooldown(proposal_cooldown) => {
+                    self.proposal_cooldown = proposal_cooldown
+                }
+                GovernorParam::MaxOpenProposals(max_open_proposals) => {
+                    self.max_open_proposals = max_open_proposals
+                }
+                GovernorParam::MaxActiveProposals(max_active_proposals) => {
+                    self.max_active_proposals = max_active_proposals
+                }
+                GovernorParam::RetentionPeriod(retention_period) => {
+                    self.retention_period = retention_period
+                }
+                GovernorParam::CountingMode(counting_mode) => {
+                    self.counting_mode = counting_mode
+                }
+                GovernorParam::VotingStrategy(voting_strategy) => {
+                    self.voting_strategy = voting_strategy
+                }
+                GovernorParam::WeightSources(ref weight_sources) => {
+                    self.weight_sources = weight_sources.clone()
+                }
+                GovernorParam::ReceiptNft(receipt_nft) => self.receipt_nft = receipt_nft,
+                GovernorParam::ReceiptTiers(ref receipt_tiers) => {
+                    self.receipt_tiers = receipt_tiers.clone()
+                }
+                GovernorParam::RewardEpoch(reward_epoch) => {
+                    self.reward_epoch = reward_epoch
+                }
+                GovernorParam::QuorumFloor(quorum_floor) => {
+                    self.config.quorum_floor = quorum_floor
+                }
+                GovernorParam::QuorumDecay(quorum_decay) => {
+                    self.config.quorum_decay = quorum_decay
+                }
+                GovernorParam::JoinRate(join_rate) => self.join_rate = join_rate,
+                GovernorParam::BudgetCap(budget_cap) => {
+                    self.budget_cap = budget_cap;
+                    self.reset_budget();
+                }
+                GovernorParam::BudgetEpoch(budget_epoch) => {
+                    self.budget_epoch = budget_epoch;
+                    self.reset_budget();
+                }
+                GovernorParam::BudgetCarryOver(budget_carry_over) => {
+                    self.budget_carry_over = budget_carry_over
+                }
+                GovernorParam::LargePayout(large_payout) => {
+                    self.large_payout = large_payout
+                }
+                GovernorParam::Emergency(emergency) => self.emergency = emergency,
+                GovernorParam::PriceOracle(price_oracle) => {
+                    self.price_oracle = price_oracle
+                }
+                GovernorParam::PayoutFee(payout_fee) => self.payout_fee = payout_fee,
+                GovernorParam::Guardian(guardian) => self.guardian = guardian,
+                GovernorParam::KillSwitchDelay(kill_switch_delay) => {
+                    self.kill_switch_delay = kill_switch_delay
+                }
+                GovernorParam::GuardianWindow(guardian_window) => {
+                    self.guardian_window = guardian_window
+                }
+                GovernorParam::GrantRole(role, account) => self.add_role(role, account),
+                GovernorParam::Blacklist(account) => {
+                    self.blacklist.insert(account, &());
+                }
+                GovernorParam::Unblacklist(account) => self.blacklist.remove(account),
+                GovernorParam::MembersOnly(members_only) => {
+                    self.members_only = members_only
+                }
+                GovernorParam::ComplianceRegistry(compliance_registry) => {
+                    self.compliance_registry = compliance_registry
+                }
+                GovernorParam::Council(ref council) => self.council = council.clone(),
+                GovernorParam::Track(track) => {
+                    self.tracks.insert(track.id, &track);
+                }
+                GovernorParam::RemoveTrack(track_id) => self.tracks.remove(track_id),
+                GovernorParam::ObjectionWindow(objection_window) => {
+                    self.objection_window = objection_window
+                }
+                GovernorParam::ObjectionThreshold(objection_threshold) => {
+                    self.objection_threshold = objection_threshold
+                }
+                GovernorParam::RevokeRole(role, account) => {
+                    self.remove_role(role, account)
+                }
+                GovernorParam::FeeReserve(fee_reserve) => self.fee_reserve = fee_reserve,
+                GovernorParam::CoSigning(ref co_signing) => {
+                    self.co_signing = co_signing.clone()
+                }
+                GovernorParam::DexRouter(dex_router) => self.dex_router = dex_router,
+                GovernorParam::StakingAdapter(staking_adapter) => {
+                    self.staking_adapter = staking_adapter
+                }
+                GovernorParam::ConvictionPeriod(conviction_period) => {
+                    self.conviction_period = conviction_period
+                }
+                GovernorParam::MaxVoteWeight(max_vote_weight) => {
+                    self.max_vote_weight = max_vote_weight
+                }
+                GovernorParam::ApprovalThreshold(approval_threshold) => {
+                    self.config.approval_threshold = approval_threshold
+                }
+                GovernorParam::CallGasLimit(call_gas_limit) => {
+                    self.call_gas_limit = call_gas_limit
+                }
+            }
+            self.env().emit_event(ParameterChanged {
+                param: param.clone(),
+            });
+        }
+
+        fn validate_duration(&self, duration: u64) -> Result<(), GovernorError> {
+            if duration == 0 {
+                return Err(GovernorError::DurationError)
+            }
+            if duration < self.config.min_duration {
+                return Err(GovernorError::DurationTooShort)
+            }
+            if duration > self.config.max_duration {
+                return Err(GovernorError::DurationTooLong)
+            }
+            Ok(())
+        }
+
+        #[cfg(not(test))]
+        fn set_code(&self, code_hash: &Hash) -> Result<(), GovernorError> {
+            self.env()
+                .set_code_hash(code_hash)
+                .map_err(|_| GovernorError::UpgradeFailed)
+        }
+
+        #[cfg(not(test))]
+        fn dispatch_call(&self, call: &ContractCall) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(call.callee)
+                .transferred_value(call.transferred_value)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(call.selector))
+                        .push_arg(CallInput(&call.input)),
+                )
+                .returns::<()>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CallFailed)?
+                .map_err(|_| GovernorError::CallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn call_router(
+            &self,
+            router: AccountId,
+            swap: &Swap,
+        ) -> Result<(), GovernorError> {
+            let value = if swap.asset_in.is_none() {
+                swap.amount_in
+            } else {
+                0
+            };
+            build_call::<DefaultEnvironment>()
+                .call(router)
+                .gas_limit(self.call_gas_limit)
+                .transferred_value(value)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "DexRouter::swap"
+                    )))
+                    .push_arg(swap.asset_in)
+                    .push_arg(swap.asset_out)
+                    .push_arg(swap.amount_in)
+                    .push_arg(swap.min_amount_out)
+                    .push_arg(self.env().account_id()),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::SwapFailed)?
+                .map_err(|_| GovernorError::SwapFailed)?;
+            Ok(())
+        }
+
+        #[cfg(not(test))]
+        fn stake(
+            &self,
+            adapter: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(adapter)
+                .gas_limit(self.call_gas_limit)
+                .transferred_value(amount)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "StakingAdapter::stake"
+                ))))
+                .returns::<()>()
+                .try_invoke()
+                .map_err(|_| GovernorError::StakingFailed)?
+                .map_err(|_| GovernorError::StakingFailed)
+        }
+
+        #[cfg(not(test))]
+        fn unstake(
+            &self,
+            adapter: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(adapter)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "StakingAdapter::unstake"
+                    )))
+                    .push_arg(amount),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::StakingFailed)?
+                .map_err(|_| GovernorError::StakingFailed)?;
+            Ok(())
+        }
+
+        #[cfg(not(test))]
+        fn mint_receipt(
+            &self,
+            receipt_nft: AccountId,
+            to: AccountId,
+            receipt_id: u32,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(receipt_nft)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP34Mintable::mint"
+                    )))
+                    .push_arg(to)
+                    .push_arg(Id::U32(receipt_id)),
+                )
+                .returns::<Result<(), openbrush::contracts::traits::psp34::PSP34Error>>()
+                .try_invoke()
+                .map_err(|_| GovernorError::ReceiptMintFailed)?
+                .map_err(|_| GovernorError::ReceiptMintFailed)?
+                .map_err(|_| GovernorError::ReceiptMintFailed)
+        }
+
+        #[cfg(not(test))]
+        fn asset_price(
+            &self,
+            oracle: AccountId,
+            asset: Option<AccountId>,
+        ) -> Option<Balance> {
+            build_call::<DefaultEnvironment>()
+                .call(oracle)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PriceOracle::price"
+                    )))
+                    .push_arg(asset),
+                )
+                .returns::<Option<Balance>>()
+                .try_invoke()
+                .ok()?
+                .ok()?
+        }
+
+        #[cfg(not(test))]
+        fn is_verified(
+            &self,
+            registry: AccountId,
+            account: AccountId,
+        ) -> Result<bool, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(registry)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "ComplianceRegistry::is_verified"
+                    )))
+                    .push_arg(account),
+                )
+                .returns::<bool>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn transfer_native(
+            &self,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| GovernorError::TransferError)
+        }
+
+        #[cfg(not(test))]
+        fn approve_token(
+            &self,
+            asset: AccountId,
+            spender: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(asset)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22::approve"
+                    )))
+                    .push_arg(spender)
+                    .push_arg(amount),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(GovernorError::from)
+        }
+
+        #[cfg(not(test))]
+        fn transfer_token(&self, transfer: &TokenTransfer) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(transfer.asset)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22::transfer"
+                    )))
+                    .push_arg(transfer.to)
+                    .push_arg(transfer.amount)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(GovernorError::from)
+        }
+
+        /// Pulls `amount` governance tokens from `from` into the governor.
+        #[cfg(not(test))]
+        fn transfer_token_from(
+            &self,
+            from: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(self.governance_token)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22::transfer_from"
+                    )))
+                    .push_arg(from)
+                    .push_arg(self.env().account_id())
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(GovernorError::from)
+        }
+
+        #[cfg(not(test))]
+        fn mint_token(
+            &self,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(self.governance_token)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22Mintable::mint"
+                    )))
+                    .push_arg(to)
+                    .push_arg(amount),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(GovernorError::from)
+        }
+
+        #[cfg(not(test))]
+        fn burn_token(
+            &self,
+            from: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(self.governance_token)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22Burnable::burn"
+                    )))
+                    .push_arg(from)
+                    .push_arg(amount),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(|_| GovernorError::TokenTransferFailed)?
+                .map_err(GovernorError::from)
+        }
+
+        #[cfg(not(test))]
+        fn total_supply(&self) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(self.governance_token)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "PSP22::total_supply"
+                ))))
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn nft_total_supply(
+            &self,
+            collection: AccountId,
+        ) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(collection)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "PSP34::total_supply"
+                ))))
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn nft_balance_of(
+            &self,
+            collection: AccountId,
+            account: AccountId,
+        ) -> Result<u32, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(collection)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP34::balance_of"
+                    )))
+                    .push_arg(account),
+                )
+                .returns::<u32>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn balance_of(&self, account: AccountId) -> Result<Balance, GovernorError> {
+            self.asset_balance_of(self.governance_token, account)
+        }
+
+        #[cfg(not(test))]
+        fn asset_balance_of(
+            &self,
+            asset: AccountId,
+            account: AccountId,
+        ) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(asset)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22::balance_of"
+                    )))
+                    .push_arg(account),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn strategy_weight(
+            &self,
+            strategy: AccountId,
+            account: AccountId,
+            snapshot: u64,
+        ) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(strategy)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "VotingStrategy::weight"
+                    )))
+                    .push_arg(account)
+                    .push_arg(snapshot),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn strategy_total_weight(
+            &self,
+            strategy: AccountId,
+            snapshot: u64,
+        ) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(strategy)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "VotingStrategy::total_weight"
+                    )))
+                    .push_arg(snapshot),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn total_supply_at(&self, timestamp: u64) -> Result<Balance, GovernorError> {
+            self.asset_total_supply_at(self.governance_token, timestamp)
+        }
+
+        #[cfg(not(test))]
+        fn balance_of_at(
+            &self,
+            account: AccountId,
+            timestamp: u64,
+        ) -> Result<Balance, GovernorError> {
+            self.asset_balance_of_at(self.governance_token, account, timestamp)
+        }
+
+        #[cfg(not(test))]
+        fn asset_total_supply_at(
+            &self,
+            asset: AccountId,
+            timestamp: u64,
+        ) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(asset)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22Snapshot::total_supply_at"
+                    )))
+                    .push_arg(timestamp),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        #[cfg(not(test))]
+        fn asset_balance_of_at(
+            &self,
+            asset: AccountId,
+            account: AccountId,
+            timestamp: u64,
+        ) -> Result<Balance, GovernorError> {
+            build_call::<DefaultEnvironment>()
+                .call(asset)
+                .gas_limit(self.call_gas_limit)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22Snapshot::balance_of_at"
+                    )))
+                    .push_arg(account)
+                    .push_arg(timestamp),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| GovernorError::CrossContractCallFailed)?
+                .map_err(|_| GovernorError::CrossContractCallFailed)
+        }
+
+        // The off-chain environment cannot dispatch cross-contract calls, so unit
+        // tests read the governance token state from `tests::token` instead.
+        #[cfg(test)]
+        fn set_code(&self, code_hash: &Hash) -> Result<(), GovernorError> {
+            tests::calls::record_code_hash(*code_hash);
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn dispatch_call(&self, call: &ContractCall) -> Result<(), GovernorError> {
+            if tests::calls::rejects_transfers(call.callee) {
+                return Err(GovernorError::CallFailed)
+            }
+            tests::calls::record(call.clone());
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn call_router(
+            &self,
+            router: AccountId,
+            swap: &Swap,
+        ) -> Result<(), GovernorError> {
+            tests::dex::swap(router, self.env().account_id(), swap)
+        }
+
+        #[cfg(test)]
+        fn stake(
+            &self,
+            adapter: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            tests::staking::stake(adapter, self.env().account_id(), amount)
+        }
+
+        #[cfg(test)]
+        fn unstake(
+            &self,
+            adapter: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            tests::staking::unstake(adapter, self.env().account_id(), amount)
+        }
+
+        #[cfg(test)]
+        fn mint_receipt(
+            &self,
+            receipt_nft: AccountId,
+            to: AccountId,
+            _receipt_id: u32,
+        ) -> Result<(), GovernorError> {
+            tests::token::set_asset_balance(
+                receipt_nft,
+                to,
+                tests::token::balance_of(receipt_nft, to) + 1,
+            );
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn asset_price(
+            &self,
+            _oracle: AccountId,
+            asset: Option<AccountId>,
+        ) -> Option<Balance> {
+            tests::oracle::price(asset)
+        }
+
+        #[cfg(test)]
+        fn is_verified(
+            &self,
+            registry: AccountId,
+            account: AccountId,
+        ) -> Result<bool, GovernorError> {
+            Ok(tests::compliance::is_verified(registry, account))
+        }
+
+        #[cfg(test)]
+        fn transfer_native(
+            &self,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            if tests::calls::rejects_transfers(to) {
+                return Err(GovernorError::TransferError)
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| GovernorError::TransferError)
+        }
+
+        #[cfg(test)]
+        fn approve_token(
+            &self,
+            _asset: AccountId,
+            _spender: AccountId,
+            _amount: Balance,
+        ) -> Result<(), GovernorError> {
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn transfer_token(&self, transfer: &TokenTransfer) -> Result<(), GovernorError> {
+            tests::token::transfer(
+                transfer.asset,
+                self.env().account_id(),
+                transfer.to,
+                transfer.amount,
+            )
+            .map_err(GovernorError::from)
+        }
+
+        #[cfg(test)]
+        fn transfer_token_from(
+            &self,
+            from: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            tests::token::transfer(
+                self.governance_token,
+                from,
+                self.env().account_id(),
+                amount,
+            )
+            .map_err(GovernorError::from)
+        }
+
+        #[cfg(test)]
+        fn mint_token(
+            &self,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            tests::token::mint(self.governance_token, to, amount);
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn burn_token(
+            &self,
+            from: AccountId,
+            amount: Balance,
+        ) -> Result<(), GovernorError> {
+            tests::token::burn(self.governance_token, from, amount);
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn total_supply(&self) -> Result<Balance, GovernorError> {
+            tests::token::ensure_reachable(self.governance_token)?;
+            Ok(tests::token::total_supply(self.governance_token))
+        }
+
+        #[cfg(test)]
+        fn balance_of(&self, account: AccountId) -> Result<Balance, GovernorError> {
+            self.asset_balance_of(self.governance_token, account)
+        }
+
+        #[cfg(test)]
+        fn asset_balance_of(
+            &self,
+            asset: AccountId,
+            account: AccountId,
+        ) -> Result<Balance, GovernorError> {
+            tests::token::ensure_reachable(asset)?;
+            Ok(tests::token::balance_of(asset, account))
+        }
+
+        #[cfg(test)]
+        fn nft_total_supply(
+            &self,
+            collection: AccountId,
+        ) -> Result<Balance, GovernorError> {
+            tests::token::ensure_reachable(collection)?;
+            Ok(tests::token::total_supply(collection))
+        }
+
+        #[cfg(test)]
+        fn nft_balance_of(
+            &self,
+            collection: AccountId,
+            account: AccountId,
+        ) -> Result<u32, GovernorError> {
+            tests::token::ensure_reachable(collection)?;
+            Ok(tests::token::balance_of(collection, account) as u32)
+        }
+
+        #[cfg(test)]
+        fn total_supply_at(&self, timestamp: u64) -> Result<Balance, GovernorError> {
+            self.asset_total_supply_at(self.governance_token, timestamp)
+        }
+
+        #[cfg(test)]
+        fn balance_of_at(
+            &self,
+            account: AccountId,
+            timestamp: u64,
+        ) -> Result<Balance, GovernorError> {
+            self.asset_balance_of_at(self.governance_token, account, timestamp)
+        }
+
+        #[cfg(test)]
+        fn asset_total_supply_at(
+            &self,
+            asset: AccountId,
+            timestamp: u64,
+        ) -> Result<Balance, GovernorError> {
+            tests::token::ensure_reachable(asset)?;
+            Ok(tests::token::total_supply_at(asset, timestamp))
+        }
+
+        #[cfg(test)]
+        fn asset_balance_of_at(
+            &self,
+            asset: AccountId,
+            account: AccountId,
+            timestamp: u64,
+        ) -> Result<Balance, GovernorError> {
+            tests::token::ensure_reachable(asset)?;
+            Ok(tests::token::balance_of_at(asset, account, timestamp))
+        }
+
+        #[cfg(test)]
+        fn strategy_weight(
+            &self,
+            strategy: AccountId,
+            account: AccountId,
+            _snapshot: u64,
+        ) -> Result<Balance, GovernorError> {
+            Ok(tests::strategy::weight(strategy, account))
+        }
+
+        #[cfg(test)]
+        fn strategy_total_weight(
+            &self,
+            strategy: AccountId,
+            _snapshot: u64,
+        ) -> Result<Balance, GovernorError> {
+            Ok(tests::strategy::total_weight(strategy))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::{
+            primitives::Key,
+            storage::traits::StorageKey,
+        };
+
+        pub(super) mod token {
+            use super::*;
+            use std::{
+                cell::RefCell,
+                collections::BTreeMap,
+            };
+
+            /// Governance token the contracts under test are created with.
+            pub const GOVERNANCE_TOKEN: [u8; 32] = [0x01; 32];
+
+            /// Asset and account of a balance, or asset alone for the total supply.
+            type CheckpointKey = (AccountId, Option<AccountId>);
+
+            thread_local! {
+                static TOTAL_SUPPLIES: RefCell<BTreeMap<AccountId, Balance>> =
+                    const { RefCell::new(BTreeMap::new()) };
+                static BALANCES: RefCell<BTreeMap<(AccountId, AccountId), Balance>> =
+                    const { RefCell::new(BTreeMap::new()) };
+                static BROKEN: RefCell<Vec<AccountId>> = const { RefCell::new(Vec::new()) };
+                /// Values by the block timestamp they were set at.
+                static CHECKPOINTS: RefCell<BTreeMap<CheckpointKey, BTreeMap<u64, Balance>>> =
+                    const { RefCell::new(BTreeMap::new()) };
+            }
+
+            fn checkpoint(key: CheckpointKey, value: Balance) {
+                let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+                CHECKPOINTS.with(|checkpoints| {
+                    checkpoints
+                        .borrow_mut()
+                        .entry(key)
+                        .or_default()
+                        .insert(now, value)
+                });
+            }
+
+            fn value_at(key: CheckpointKey, timestamp: u64) -> Balance {
+                CHECKPOINTS.with(|checkpoints| {
+                    checkpoints
+                        .borrow()
+                        .get(&key)
+                        .and_then(|values| values.range(..=timestamp).next_back())
+                        .map(|(_, value)| *value)
+                        .unwrap_or_default()
+                })
+            }
+
+            pub fn total_supply_at(asset: AccountId, timestamp: u64) -> Balance {
+                value_at((asset, None), timestamp)
+            }
+
+            pub fn balance_of_at(
+                asset: AccountId,
+                account: AccountId,
+                timestamp: u64,
+            ) -> Balance {
+                value_at((asset, Some(account)), timestamp)
+            }
+
+            /// Makes queries to `asset` fail, like a token that traps or does not
+            /// implement PSP22.
+            pub fn set_broken(asset: AccountId) {
+                BROKEN.with(|broken| broken.borrow_mut().push(asset));
+            }
+
+            pub fn ensure_reachable(asset: AccountId) -> Result<(), GovernorError> {
+                if BROKEN.with(|broken| broken.borrow().contains(&asset)) {
+                    return Err(GovernorError::CrossContractCallFailed)
+                }
+                Ok(())
+            }
+
+            pub fn total_supply(asset: AccountId) -> Balance {
+                TOTAL_SUPPLIES.with(|supplies| {
+                    supplies.borrow().get(&asset).copied().unwrap_or_default()
+                })
+            }
+
+            pub fn balance_of(asset: AccountId, account: AccountId) -> Balance {
+                BALANCES.with(|balances| {
+                    balances
+                        .borrow()
+                        .get(&(asset, account))
+                        .copied()
+                        .unwrap_or_default()
+                })
+            }
+
+            pub fn transfer(
+                asset: AccountId,
+                from: AccountId,
+                to: AccountId,
+                value: Balance,
+            ) -> Result<(), PSP22Error> {
+                let from_balance = balance_of(asset, from);
+                if from_balance < value {
+                    return Err(PSP22Error::InsufficientBalance)
+                }
+                set_asset_balance(asset, from, from_balance - value);
+                set_asset_balance(asset, to, balance_of(asset, to) + value);
+                Ok(())
+            }
+
+            pub fn mint(asset: AccountId, to: AccountId, value: Balance) {
+                set_asset_supply(asset, total_supply(asset) + value);
+                set_asset_balance(asset, to, balance_of(asset, to) + value);
+            }
+
+            pub fn burn(asset: AccountId, from: AccountId, value: Balance) {
+                set_asset_supply(asset, total_supply(asset) - value);
+                set_asset_balance(asset, from, balance_of(asset, from) - value);
+            }
+
+            pub fn set_total_supply(total_supply: Balance) {
+                set_asset_supply(AccountId::from(GOVERNANCE_TOKEN), total_supply);
+            }
+
+            pub fn set_asset_supply(asset: AccountId, total_supply: Balance) {
+                TOTAL_SUPPLIES
+                    .with(|supplies| supplies.borrow_mut().insert(asset, total_supply));
+                checkpoint((asset, None), total_supply);
+            }
+
+            pub fn set_balance_of(account: AccountId, balance: Balance) {
+                set_asset_balance(AccountId::from(GOVERNANCE_TOKEN), account, balance);
+            }
+
+            pub fn set_asset_balance(
+                asset: AccountId,
+                account: AccountId,
+                balance: Balance,
+            ) {
+                BALANCES.with(|balances| {
+                    balances.borrow_mut().insert((asset, account), balance)
+                });
+                checkpoint((asset, Some(account)), balance);
+            }
+        }
+
+        const MIN_DURATION: u64 = 1;
+        const MAX_DURATION: u64 = 60;
+        const GRACE_PERIOD: u64 = 10;
+        const TIMELOCK_DELAY: u64 = 5;
+
+        pub(super) mod calls {
+            use super::*;
+            use std::cell::RefCell;
+
+            thread_local! {
+                static DISPATCHED: RefCell<Vec<ContractCall>> = const { RefCell::new(Vec::new()) };
+                static CODE_HASH: RefCell<Option<Hash>> = const { RefCell::new(None) };
+                static REJECTING: RefCell<Vec<AccountId>> = const { RefCell::new(Vec::new()) };
+                static WATCHED: RefCell<Option<Key>> = const { RefCell::new(None) };
+                static OBSERVED: RefCell<Vec<Option<bool>>> = const { RefCell::new(Vec::new()) };
+            }
+
+            /// Makes every call read the governor's flag stored at `key`, as the
+            /// callee would see it if it called back.
+            pub fn watch(key: Key) {
+                WATCHED.with(|watched| *watched.borrow_mut() = Some(key));
+            }
+
+            pub fn observed() -> Vec<Option<bool>> {
+                OBSERVED.with(|observed| observed.borrow().clone())
+            }
+
+            /// Makes native transfers and calls to `account` fail, like a contract
+            /// that traps.
+            pub fn reject_transfers(account: AccountId, reject: bool) {
+                REJECTING.with(|rejecting| {
+                    let mut rejecting = rejecting.borrow_mut();
+                    rejecting.retain(|rejected| *rejected != account);
+                    if reject {
+                        rejecting.push(account);
+                    }
+                });
+            }
+
+            pub fn rejects_transfers(account: AccountId) -> bool {
+                REJECTING.with(|rejecting| rejecting.borrow().contains(&account))
+            }
+
+            pub fn record_code_hash(code_hash: Hash) {
+                CODE_HASH.with(|hash| *hash.borrow_mut() = Some(code_hash));
+            }
+
+            pub fn code_hash() -> Option<Hash> {
+                CODE_HASH.with(|hash| *hash.borrow())
+            }
+
+            pub fn record(call: ContractCall) {
+                if let Some(key) = WATCHED.with(|watched| *watched.borrow()) {
+                    let flag = ink::env::get_contract_storage::<Key, bool>(&key)
+                        .ok()
+                        .flatten();
+                    OBSERVED.with(|observed| observed.borrow_mut().push(flag));
+                }
+                DISPATCHED.with(|calls| calls.borrow_mut().push(call));
+            }
+
+            pub fn dispatched() -> Vec<ContractCall> {
+                DISPATCHED.with(|calls| calls.borrow().clone())
+            }
+        }
+
+        pub(super) mod strategy {
+            use super::*;
+            use std::{
+                cell::RefCell,
+                collections::BTreeMap,
+            };
+
+            thread_local! {
+                static WEIGHTS: RefCell<BTreeMap<(AccountId, AccountId), Balance>> =
+                    const { RefCell::new(BTreeMap::new()) };
+                static TOTAL_WEIGHTS: RefCell<BTreeMap<AccountId, Balance>> =
+                    const { RefCell::new(BTreeMap::new()) };
+            }
+
+            pub fn set_weight(strategy: AccountId, account: AccountId, weight: Balance) {
+                WEIGHTS.with(|weights| {
+                    weights.borrow_mut().insert((strategy, account), weight)
+                });
+            }
+
+            pub fn set_total_weight(strategy: AccountId, weight: Balance) {
+                TOTAL_WEIGHTS
+                    .with(|weights| weights.borrow_mut().insert(strategy, weight));
+            }
+
+            pub fn weight(strategy: AccountId, account: AccountId) -> Balance {
+                WEIGHTS.with(|weights| {
+                    weights
+                        .borrow()
+                        .get(&(strategy, account))
+                        .copied()
+                        .unwrap_or(0)
+                })
+            }
+
+            pub fn total_weight(strategy: AccountId) -> Balance {
+                TOTAL_WEIGHTS
+                    .with(|weights| weights.borrow().get(&strategy).copied().unwrap_or(0))
+            }
+        }
+
+        /// Router paying `rate` percent of the input in the output token.
+        pub(super) mod dex {
+            use super::*;
+            use std::cell::RefCell;
+
+            thread_local! {
+                static RATE: RefCell<Balance> = const { RefCell::new(0) };
+            }
+
+            pub fn set_rate(rate: Balance) {
+                RATE.with(|value| *value.borrow_mut() = rate);
+            }
+
+            pub fn swap(
+                router: AccountId,
+                to: AccountId,
+                swap: &Swap,
+            ) -> Result<(), GovernorError> {
+                let Some(asset_out) = swap.asset_out else {
+                    return Err(GovernorError::SwapFailed)
+                };
+                match swap.asset_in {
+                    Some(asset_in) => {
+                        token::transfer(asset_in, to, router, swap.amount_in)?
+                    }
+                    None => {
+                        set_balance(to, get_balance(to) - swap.amount_in);
+                        set_balance(router, get_balance(router) + swap.amount_in);
+                    }
+                }
+                let amount_out = swap.amount_in * RATE.with(|rate| *rate.borrow()) / 100;
+                token::set_asset_balance(
+                    asset_out,
+                    to,
+                    token::balance_of(asset_out, to) + amount_out,
+                );
+                Ok(())
+            }
+        }
+
+        pub(super) mod oracle {
+            use super::*;
+            use std::{
+                cell::RefCell,
+                collections::HashMap,
+            };
+
+            thread_local! {
+                static PRICES: RefCell<HashMap<Option<AccountId>, Balance>> =
+                    RefCell::new(HashMap::new());
+            }
+
+            pub fn set_price(asset: Option<AccountId>, price: Balance) {
+                PRICES.with(|prices| prices.borrow_mut().insert(asset, price));
+            }
+
+            pub fn price(asset: Option<AccountId>) -> Option<Balance> {
+                PRICES.with(|prices| prices.borrow().get(&asset).copied())
+            }
+        }
+
+        pub(super) mod compliance {
+            use super::*;
+            use std::cell::RefCell;
+
+            thread_local! {
+                static VERIFIED: RefCell<Vec<(AccountId, AccountId)>> =
+                    const { RefCell::new(Vec::new()) };
+            }
+
+            pub fn verify(registry: AccountId, account: AccountId) {
+                VERIFIED.with(|verified| verified.borrow_mut().push((registry, account)));
+            }
+
+            pub fn is_verified(registry: AccountId, account: AccountId) -> bool {
+                VERIFIED.with(|verified| verified.borrow().contains(&(registry, account)))
+            }
+        }
+
+        /// Adapter paying `reward` percent on top of unstaked funds.
+        pub(super) mod staking {
+            use super::*;
+            use std::cell::RefCell;
+
+            thread_local! {
+                static REWARD: RefCell<Balance> = const { RefCell::new(0) };
+            }
+
+            pub fn set_reward(reward: Balance) {
+                REWARD.with(|value| *value.borrow_mut() = reward);
+            }
+
+            pub fn stake(
+                adapter: AccountId,
+                from: AccountId,
+                amount: Balance,
+            ) -> Result<(), GovernorError> {
+                set_balance(from, get_balance(from) - amount);
+                set_balance(adapter, get_balance(adapter) + amount);
+                Ok(())
+            }
+
+            pub fn unstake(
+                adapter: AccountId,
+                to: AccountId,
+                amount: Balance,
+            ) -> Result<(), GovernorError> {
+                let amount =
+                    amount + amount * REWARD.with(|reward| *reward.borrow()) / 100;
+                set_balance(adapter, get_balance(adapter).saturating_sub(amount));
+                set_balance(to, get_balance(to) + amount);
+                Ok(())
+            }
+        }
+
+        fn create_contract(initial_balance: Balance) -> Governor {
+            let accounts = default_accounts();
+            set_sender(accounts.alice);
+            set_balance(contract_id(), initial_balance);
+            Governor::new(
+                AccountId::from(token::GOVERNANCE_TOKEN),
+                5_000,
+                0,
+                MIN_DURATION,
+                MAX_DURATION,
+                GRACE_PERIOD,
+                TIMELOCK_DELAY,
+                0,
+                None,
+            )
+        }
+
+        fn contract_id() -> AccountId {
+            ink::env::test::callee::<ink::env::DefaultEnvironment>()
+        }
+
+        /// Proposes a call into the governor itself and takes it through voting,
+        /// the timelock and `execute`. The caller needs a majority of the supply.
+        fn execute_self_call(
+            governor: &mut Governor,
+            selector: [u8; 4],
+            input: Vec<u8>,
+        ) -> Result<(), GovernorError> {
+            let proposal_id = governor.next_proposal_id();
+            let call = ContractCall {
+                callee: contract_id(),
+                selector,
+                input,
+                transferred_value: 0,
+            };
+            governor.propose_call(call, 1, Hash::default())?;
+            governor.vote(proposal_id, VoteType::For)?;
+            set_now(governor.get_proposal(proposal_id).unwrap().vote_end + 1);
+            governor.queue(proposal_id)?;
+            set_now(governor.get_proposal(proposal_id).unwrap().eta.unwrap());
+            governor.execute(proposal_id)
+        }
+
+        fn default_accounts(
+        ) -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_sender(sender: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(sender);
+        }
 
         fn set_balance(account_id: AccountId, balance: Balance) {
             ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
@@ -123,42 +7781,4009 @@ pub mod dao {
             )
         }
 
+        fn get_balance(account_id: AccountId) -> Balance {
+            ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(
+                account_id,
+            )
+            .unwrap()
+        }
+
+        fn set_value_transferred(value: Balance) {
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(value);
+        }
+
+        fn set_now(timestamp: u64) {
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                timestamp,
+            );
+        }
+
+        #[ink::test]
+        #[allow(clippy::identity_op)]
+        fn propose_works() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            assert_eq!(
+                governor.propose(accounts.django, 0, 1, Hash::default()),
+                Err(GovernorError::AmountShouldNotBeZero)
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 0, Hash::default()),
+                Err(GovernorError::DurationError)
+            );
+            let result = governor.propose(accounts.django, 100, 1, Hash::default());
+            assert_eq!(result, Ok(()));
+            let proposal = governor.get_proposal(0).unwrap();
+            let now = governor.now();
+            assert_eq!(
+                proposal,
+                Proposal {
+                    proposer: accounts.alice,
+                    kind: ProposalKind::NativeTransfer(vec![Transfer {
+                        to: accounts.django,
+                        amount: 100,
+                    }]),
+                    vote_start: 0,
+                    vote_end: now + 1 * ONE_MINUTE,
+                    description_hash: Hash::default(),
+                    executed: false,
+                    canceled: false,
+                    expires_at: now + (1 + GRACE_PERIOD) * ONE_MINUTE,
+                    eta: None,
+                    bond: 0,
+                    bond_settled: false,
+                    counting_mode: CountingMode::Linear,
+                    voting_strategy: None,
+                    weight_sources: Vec::new(),
+                    large_payout: false,
+                    track: None,
+                }
+            );
+            assert_eq!(governor.next_proposal_id(), 1);
+        }
+
+        #[ink::test]
+        fn quorum_not_reached() {
+            let mut governor = create_contract(1000);
+            let result =
+                governor.propose(AccountId::from([0x02; 32]), 100, 1, Hash::default());
+            assert_eq!(result, Ok(()));
+            let execute = governor.execute(0);
+            assert_eq!(execute, Err(GovernorError::QuorumNotReached));
+        }
+
+        #[ink::test]
+        fn vote_and_execute_works() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 100);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert!(governor.has_voted(0, accounts.alice));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 100,
+                    for_votes: 600,
+                    abstain_votes: 0,
+                })
+            );
+
+            assert_eq!(governor.execute(0), Err(GovernorError::VotePeriodNotEnded));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(
+                governor.vote(0, VoteType::For),
+                Err(GovernorError::VotePeriodEnded)
+            );
+
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            let django_balance = get_balance(accounts.django);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance + 100);
+            assert_eq!(
+                governor.execute(0),
+                Err(GovernorError::ProposalAlreadyExecuted)
+            );
+        }
+
+        #[ink::test]
+        fn weight_for_balance_works() {
+            let governor = create_contract(1000);
+            assert_eq!(governor.weight_for_balance(500), 0);
+
+            token::set_total_supply(1000);
+            assert_eq!(governor.weight_for_balance(0), 0);
+            assert_eq!(governor.weight_for_balance(9), 9);
+            assert_eq!(governor.weight_for_balance(505), 505);
+            assert_eq!(governor.weight_for_balance(1000), 1000);
+        }
+
+        #[ink::test]
+        fn weight_is_clamped_when_balance_exceeds_supply() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(100);
+            token::set_balance_of(accounts.alice, 300);
+            assert_eq!(governor.weight_for_balance(300), 100);
+
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 0,
+                    for_votes: 100,
+                    abstain_votes: 0,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn edit_proposal_works_before_voting_starts() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            assert_eq!(
+                governor.propose_with_delay(accounts.django, 100, 1, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(
+                governor.edit_proposal(0, accounts.eve, 0),
+                Err(GovernorError::AmountShouldNotBeZero)
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.edit_proposal(0, accounts.eve, 200),
+                Err(GovernorError::OnlyProposer)
+            );
+            set_sender(accounts.alice);
+            assert_eq!(governor.edit_proposal(0, accounts.eve, 200), Ok(()));
+            let proposal = governor.get_proposal(0).unwrap();
+            assert_eq!(
+                proposal.kind,
+                ProposalKind::NativeTransfer(vec![Transfer {
+                    to: accounts.eve,
+                    amount: 200,
+                }])
+            );
+
+            set_now(ONE_MINUTE);
+            assert_eq!(
+                governor.edit_proposal(0, accounts.django, 100),
+                Err(GovernorError::VotingAlreadyStarted)
+            );
+        }
+
+        #[ink::test]
+        fn edit_proposal_fails_once_voting_is_open() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.edit_proposal(0, accounts.eve, 200),
+                Err(GovernorError::VotingAlreadyStarted)
+            );
+            assert_eq!(
+                governor.edit_proposal(1, accounts.eve, 200),
+                Err(GovernorError::ProposalNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn cancel_works() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            set_sender(accounts.charlie);
+            assert_eq!(governor.cancel(0), Err(GovernorError::OnlyProposer));
+            set_sender(accounts.bob);
+            assert_eq!(governor.cancel(0), Ok(()));
+            assert!(governor.get_proposal(0).unwrap().canceled);
+            assert_eq!(governor.cancel(0), Err(GovernorError::ProposalCanceled));
+            assert_eq!(
+                governor.vote(0, VoteType::For),
+                Err(GovernorError::ProposalCanceled)
+            );
+            assert_eq!(governor.execute(0), Err(GovernorError::ProposalCanceled));
+
+            // The admin (deployer) may cancel any proposal.
+            set_sender(accounts.alice);
+            assert_eq!(governor.cancel(1), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn get_state_works() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(governor.get_state(0), Err(GovernorError::ProposalNotFound));
+            for _ in 0..3 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Active));
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            assert_eq!(governor.cancel(2), Ok(()));
+            assert_eq!(governor.get_state(2), Ok(ProposalState::Canceled));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Defeated));
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Queued));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Executed));
+        }
+
+        #[ink::test]
+        fn execute_fails_after_grace_period() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+
+            let expires_at = governor.get_proposal(0).unwrap().expires_at;
+            assert_eq!(expires_at, (1 + GRACE_PERIOD) * ONE_MINUTE);
+            set_now(expires_at);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            set_now(expires_at + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Expired));
+            assert_eq!(governor.execute(0), Err(GovernorError::ProposalExpired));
+        }
+
+        #[ink::test]
+        fn execute_requires_elapsed_timelock() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.queue(0), Err(GovernorError::VotePeriodNotEnded));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.execute(0), Err(GovernorError::ProposalNotQueued));
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(0), Err(GovernorError::ProposalAlreadyQueued));
+
+            let proposal = governor.get_proposal(0).unwrap();
+            let eta = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            assert_eq!(proposal.eta, Some(eta));
+            assert_eq!(proposal.expires_at, eta + GRACE_PERIOD * ONE_MINUTE);
+            set_now(eta - 1);
+            assert_eq!(governor.execute(0), Err(GovernorError::TimelockNotElapsed));
+            set_now(eta);
+            assert_eq!(governor.execute(0), Ok(()));
+        }
+
+        #[ink::test]
+        fn voting_delay_works() {
+            let accounts = default_accounts();
+            let mut governor = Governor::new(
+                AccountId::from([0x01; 32]),
+                5_000,
+                2,
+                MIN_DURATION,
+                MAX_DURATION,
+                GRACE_PERIOD,
+                TIMELOCK_DELAY,
+                0,
+                None,
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose_with_delay(accounts.django, 100, 1, 0, Hash::default()),
+                Ok(())
+            );
+
+            let proposal = governor.get_proposal(0).unwrap();
+            assert_eq!(proposal.vote_start, 2 * ONE_MINUTE);
+            assert_eq!(proposal.vote_end, 3 * ONE_MINUTE);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Pending));
+            assert_eq!(
+                governor.vote(0, VoteType::For),
+                Err(GovernorError::VoteNotStarted)
+            );
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+
+            set_now(2 * ONE_MINUTE);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Active));
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+        }
+
+        #[ink::test]
+        fn propose_checks_duration_bounds() {
+            let accounts = default_accounts();
+            let mut governor = Governor::new(
+                AccountId::from([0x01; 32]),
+                5_000,
+                0,
+                5,
+                10,
+                GRACE_PERIOD,
+                TIMELOCK_DELAY,
+                0,
+                None,
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 0, Hash::default()),
+                Err(GovernorError::DurationError)
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 4, Hash::default()),
+                Err(GovernorError::DurationTooShort)
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 11, Hash::default()),
+                Err(GovernorError::DurationTooLong)
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 10, Hash::default()),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn description_hash_is_stored() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let description_hash = Hash::from([0x07; 32]);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, description_hash),
+                Ok(())
+            );
+            assert_eq!(
+                governor.get_proposal(0).unwrap().description_hash,
+                description_hash
+            );
+        }
+
+        #[ink::test]
+        fn batch_proposal_pays_every_recipient() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose_batch(Vec::new(), 1, Hash::default()),
+                Err(GovernorError::EmptyProposal)
+            );
+            let transfers = vec![
+                Transfer {
+                    to: accounts.django,
+                    amount: 100,
+                },
+                Transfer {
+                    to: accounts.eve,
+                    amount: 0,
+                },
+            ];
+            assert_eq!(
+                governor.propose_batch(transfers, 1, Hash::default()),
+                Err(GovernorError::AmountShouldNotBeZero)
+            );
+
+            let transfers = vec![
+                Transfer {
+                    to: accounts.django,
+                    amount: 100,
+                },
+                Transfer {
+                    to: accounts.eve,
+                    amount: 250,
+                },
+            ];
+            assert_eq!(
+                governor.propose_batch(transfers, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+
+            let django_balance = get_balance(accounts.django);
+            let eve_balance = get_balance(accounts.eve);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance + 100);
+            assert_eq!(get_balance(accounts.eve), eve_balance + 250);
+            assert_eq!(get_balance(contract_id()), 650);
+        }
+
+        #[ink::test]
+        fn call_proposal_dispatches_call_on_execute() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let call = ContractCall {
+                callee: AccountId::from([0x0a; 32]),
+                selector: ink::selector_bytes!("PSP22::transfer"),
+                input: (accounts.django, 10 as Balance, Vec::<u8>::new()).encode(),
+                transferred_value: 0,
+            };
+            assert_eq!(
+                governor.propose_call(call.clone(), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.get_proposal(0).unwrap().kind,
+                ProposalKind::GenericCall(call.clone())
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            calls::reject_transfers(call.callee, true);
+            assert_eq!(governor.execute(0), Err(GovernorError::CallFailed));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Queued));
+            calls::reject_transfers(call.callee, false);
+            assert!(calls::dispatched().is_empty());
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(calls::dispatched(), vec![call]);
+        }
+
+        #[ink::test]
+        fn token_transfer_proposal_pays_out_asset() {
+            let accounts = default_accounts();
+            let asset = AccountId::from([0x09; 32]);
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_asset_balance(asset, contract_id(), 500);
+            assert_eq!(
+                governor.propose_token_transfer(
+                    asset,
+                    accounts.django,
+                    0,
+                    1,
+                    Hash::default()
+                ),
+                Err(GovernorError::AmountShouldNotBeZero)
+            );
+            assert_eq!(
+                governor.propose_token_transfer(
+                    asset,
+                    accounts.django,
+                    300,
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose_token_transfer(
+                    asset,
+                    accounts.eve,
+                    300,
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(token::balance_of(asset, accounts.django), 300);
+            assert_eq!(token::balance_of(asset, contract_id()), 200);
+            assert_eq!(
+                governor.execute(1),
+                Err(GovernorError::InsufficientTokenBalance)
+            );
+        }
+
+        #[ink::test]
+        fn edit_proposal_only_applies_to_native_transfers() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let kind = ProposalKind::TokenTransfer(vec![TokenTransfer {
+                asset: AccountId::from([0x09; 32]),
+                to: accounts.django,
+                amount: 100,
+            }]);
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::TokenTransfer(Vec::new()),
+                    1,
+                    Hash::default()
+                ),
+                Err(GovernorError::EmptyProposal)
+            );
+            assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            // Keep the proposal pending so only the kind check can fail.
+            let mut proposal = governor.get_proposal(0).unwrap();
+            proposal.vote_start = ONE_MINUTE;
+            governor.proposals.insert(0, &proposal);
+            assert_eq!(
+                governor.edit_proposal(0, accounts.eve, 100),
+                Err(GovernorError::InvalidProposalKind)
+            );
+        }
+
+        #[ink::test]
+        fn param_change_proposal_updates_governor() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::ParamChange(vec![GovernorParam::Quorum(10_001)]),
+                    1,
+                    Hash::default()
+                ),
+                Err(GovernorError::InvalidParameter)
+            );
+            let params = vec![
+                GovernorParam::Quorum(2_000),
+                GovernorParam::VotingDelay(3),
+                GovernorParam::MaxDuration(120),
+            ];
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::ParamChange(params),
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            assert_eq!(governor.config.quorum, 2_000);
+            assert_eq!(governor.config.voting_delay, 3);
+            assert_eq!(governor.config.max_duration, 120);
+            assert_eq!(governor.config.min_duration, MIN_DURATION);
+        }
+
+        #[ink::test]
+        fn upgrade_proposal_sets_code_hash() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let code_hash = Hash::from([0x42; 32]);
+            assert_eq!(governor.migrate(), Err(GovernorError::NoMigrationPending));
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::Upgrade(code_hash),
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            assert_eq!(calls::code_hash(), Some(code_hash));
+            assert_eq!(governor.migrate(), Ok(()));
+            assert_eq!(governor.migrate(), Err(GovernorError::NoMigrationPending));
+        }
+
+        #[ink::test]
+        fn amend_proposal_works_while_pending() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            assert_eq!(
+                governor.propose_with_delay(accounts.django, 100, 1, 1, Hash::default()),
+                Ok(())
+            );
+            let kind = ProposalKind::TokenTransfer(vec![TokenTransfer {
+                asset: AccountId::from([0x09; 32]),
+                to: accounts.eve,
+                amount: 50,
+            }]);
+            let description_hash = Hash::from([0x07; 32]);
+            assert_eq!(
+                governor.amend_proposal(
+                    0,
+                    ProposalKind::NativeTransfer(Vec::new()),
+                    description_hash
+                ),
+                Err(GovernorError::EmptyProposal)
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.amend_proposal(0, kind.clone(), description_hash),
+                Err(GovernorError::OnlyProposer)
+            );
+            set_sender(accounts.alice);
+            assert_eq!(
+                governor.amend_proposal(0, kind.clone(), description_hash),
+                Ok(())
+            );
+            let proposal = governor.get_proposal(0).unwrap();
+            assert_eq!(proposal.kind, kind);
+            assert_eq!(proposal.description_hash, description_hash);
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+
+            set_now(ONE_MINUTE);
+            assert_eq!(
+                governor.amend_proposal(0, kind, Hash::default()),
+                Err(GovernorError::VotingAlreadyStarted)
+            );
+        }
+
+        #[ink::test]
+        fn recurring_payout_is_claimed_per_period() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let payout = RecurringPayout {
+                to: accounts.django,
+                amount: 100,
+                period: 60,
+                periods: 3,
+            };
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::Recurring(RecurringPayout {
+                        periods: 0,
+                        ..payout.clone()
+                    }),
+                    1,
+                    Hash::default()
+                ),
+                Err(GovernorError::InvalidSchedule)
+            );
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::Recurring(payout),
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            set_sender(accounts.eve);
+            assert_eq!(
+                governor.claim_recurring(0),
+                Err(GovernorError::NotRecipient)
+            );
+            set_sender(accounts.django);
+            assert_eq!(governor.claim_recurring(0), Ok(100));
+            assert_eq!(
+                governor.claim_recurring(0),
+                Err(GovernorError::NothingToClaim)
+            );
+
+            set_now(start + 60 * ONE_MINUTE * 5);
+            assert_eq!(governor.claim_recurring(0), Ok(200));
+            assert_eq!(governor.get_payout_schedule(0).unwrap().claimed_periods, 3);
+            assert_eq!(
+                governor.claim_recurring(0),
+                Err(GovernorError::NothingToClaim)
+            );
+            assert_eq!(get_balance(contract_id()), 700);
+        }
+
+        #[ink::test]
+        fn milestone_tranches_are_released_by_follow_up_votes() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let grant = MilestoneGrant {
+                to: accounts.django,
+                amounts: vec![100, 200],
+            };
+            let kinds = [
+                ProposalKind::Milestones(grant),
+                ProposalKind::ReleaseMilestone(0),
+                ProposalKind::ReleaseMilestone(0),
+                ProposalKind::ReleaseMilestone(0),
+            ];
+            for kind in kinds {
+                assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            }
+            for proposal_id in 0..4 {
+                assert_eq!(governor.vote(proposal_id, VoteType::For), Ok(()));
+            }
+            set_now(ONE_MINUTE + 1);
+            for proposal_id in 0..4 {
+                assert_eq!(governor.queue(proposal_id), Ok(()));
+            }
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+
+            assert_eq!(
+                governor.execute(1),
+                Err(GovernorError::MilestoneGrantNotFound)
+            );
+            let django_balance = get_balance(accounts.django);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance);
+
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance + 100);
+            assert_eq!(governor.execute(2), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance + 300);
+            assert_eq!(governor.get_milestone_grant(0).unwrap().released, 2);
+            assert_eq!(
+                governor.execute(3),
+                Err(GovernorError::AllMilestonesReleased)
+            );
+        }
+
+        #[ink::test]
+        fn proposal_bond_is_refunded_or_slashed() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.proposal_bond = 50;
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::IncorrectBond)
+            );
+            set_value_transferred(50);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            set_value_transferred(0);
+            assert_eq!(governor.get_proposal(0).unwrap().bond, 50);
+            assert_eq!(governor.treasury_balance(), 900);
+
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(
+                governor.settle_bond(0),
+                Err(GovernorError::VotePeriodNotEnded)
+            );
+            set_now(ONE_MINUTE + 1);
+
+            // The off-chain environment does not move transferred value, so only the
+            // refund is visible on the balances.
+            let bob_balance = get_balance(accounts.bob);
+            assert_eq!(governor.settle_bond(0), Ok(()));
+            assert_eq!(get_balance(accounts.bob), bob_balance + 50);
+            assert_eq!(governor.treasury_balance(), 900);
+            assert_eq!(
+                governor.settle_bond(0),
+                Err(GovernorError::BondAlreadySettled)
+            );
+            assert_eq!(governor.settle_bond(1), Ok(()));
+            assert_eq!(get_balance(accounts.bob), bob_balance + 50);
+            assert_eq!(governor.treasury_balance(), 950);
+        }
+
+        #[ink::test]
+        fn propose_requires_threshold_balance() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_balance_of(accounts.alice, 100);
+            token::set_balance_of(accounts.bob, 99);
+            governor.config.proposal_threshold = 100;
+
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::BelowProposalThreshold)
+            );
+        }
+
+        #[ink::test]
+        fn proposers_are_rate_limited() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            governor.proposal_cooldown = 2;
+            governor.max_active_proposals = 1;
+
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Ok(())
+            );
+            set_now(ONE_MINUTE);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Err(GovernorError::ProposalCooldownActive)
+            );
+            set_now(2 * ONE_MINUTE);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Err(GovernorError::TooManyActiveProposals)
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Ok(())
+            );
+
+            set_sender(accounts.alice);
+            assert_eq!(governor.cancel(0), Ok(()));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.get_proposer_activity(accounts.alice),
+                ProposerActivity {
+                    last_proposed_at: Some(2 * ONE_MINUTE),
+                    open_proposals: vec![2],
+                }
+            );
+        }
+
+        #[ink::test]
+        fn finished_proposals_are_pruned_after_retention() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.retention_period = 5;
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+
+            let expires_at = governor.get_proposal(0).unwrap().expires_at;
+            set_now(expires_at + 5 * ONE_MINUTE);
+            assert_eq!(governor.prune(vec![0, 1]), 0);
+
+            set_now(expires_at + 5 * ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Expired));
+            assert_eq!(governor.prune(vec![0, 1, 2]), 2);
+            assert_eq!(governor.get_proposal(0), None);
+            assert_eq!(governor.get_proposal_vote(1), None);
+            assert!(!governor.has_voted(0, accounts.alice));
+            assert_eq!(ink::env::test::recorded_events().count(), 6);
+        }
+
+        #[ink::test]
+        fn abstain_counts_toward_quorum_only() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            token::set_balance_of(accounts.bob, 450);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Abstain), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::Abstain), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 0,
+                    for_votes: 100,
+                    abstain_votes: 450,
+                })
+            );
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Defeated));
+            assert_eq!(governor.queue(1), Err(GovernorError::ProposalNotAccepted));
+        }
+
+        #[ink::test]
+        fn voting_power_is_taken_at_vote_start() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose_with_delay(accounts.django, 100, 1, 1, Hash::default()),
+                Ok(())
+            );
+
+            set_now(ONE_MINUTE);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(
+                token::transfer(
+                    AccountId::from(token::GOVERNANCE_TOKEN),
+                    accounts.alice,
+                    accounts.bob,
+                    600
+                ),
+                Ok(())
+            );
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 0,
+                    for_votes: 600,
+                    abstain_votes: 0,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn delegate_votes_with_delegated_weight() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            token::set_balance_of(accounts.bob, 200);
+            token::set_balance_of(accounts.charlie, 300);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.delegate(accounts.bob),
+                Err(GovernorError::SelfDelegation)
+            );
+            assert_eq!(governor.delegate(accounts.alice), Ok(()));
+            assert_eq!(
+                governor.vote(0, VoteType::Against),
+                Err(GovernorError::VotingPowerDelegated)
+            );
+            set_sender(accounts.charlie);
+            assert_eq!(governor.delegate(accounts.alice), Ok(()));
+            assert_eq!(
+                governor.vote(1, VoteType::Against),
+                Err(GovernorError::VotingPowerDelegated)
+            );
+            assert_eq!(governor.undelegate(), Ok(()));
+            assert_eq!(governor.undelegate(), Err(GovernorError::NotDelegated));
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            assert_eq!(governor.get_delegators(accounts.alice), vec![accounts.bob]);
+
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            assert!(governor.has_voted(0, accounts.bob));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 300);
+            assert_eq!(
+                governor.get_proposal_vote(1),
+                Some(ProposalVote {
+                    against_votes: 300,
+                    for_votes: 300,
+                    abstain_votes: 0,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn delegation_is_transitive() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            token::set_balance_of(accounts.bob, 200);
+            token::set_balance_of(accounts.charlie, 300);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.delegate(accounts.bob), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.delegate(accounts.charlie), Ok(()));
+            set_sender(accounts.charlie);
+            assert_eq!(
+                governor.delegate(accounts.alice),
+                Err(GovernorError::DelegationCycle)
+            );
+            assert_eq!(governor.resolve_delegate(accounts.alice), accounts.charlie);
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert!(governor.has_voted(0, accounts.alice));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 600);
+        }
+
+        #[ink::test]
+        fn delegation_chain_length_is_bounded() {
+            let mut governor = create_contract(1000);
+            let chain: Vec<AccountId> = (0..=MAX_DELEGATION_HOPS as u8 + 1)
+                .map(|i| AccountId::from([0x10 + i; 32]))
+                .collect();
+            for pair in chain[1..].windows(2) {
+                set_sender(pair[0]);
+                assert_eq!(governor.delegate(pair[1]), Ok(()));
+            }
+            set_sender(chain[0]);
+            assert_eq!(
+                governor.delegate(chain[1]),
+                Err(GovernorError::DelegationChainTooLong)
+            );
+        }
+
+        #[ink::test]
+        fn votes_can_be_changed_or_retracted_while_open() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            token::set_balance_of(accounts.bob, 200);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.retract_vote(0),
+                Err(GovernorError::NoVoteToRetract)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.delegate(accounts.alice), Ok(()));
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 0,
+                    for_votes: 300,
+                    abstain_votes: 0,
+                })
+            );
+
+            assert_eq!(governor.retract_vote(0), Ok(()));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 0);
+            assert!(!governor.has_voted(0, accounts.bob));
+            set_sender(accounts.bob);
+            assert_eq!(governor.undelegate(), Ok(()));
+            assert_eq!(governor.vote(0, VoteType::Abstain), Ok(()));
+            assert_eq!(
+                governor.get_ballot(0, accounts.bob),
+                Some(Ballot {
+                    vote: VoteType::Abstain,
+                    weight: 200,
+                    delegators: vec![],
+                    reason_hash: None,
+                })
+            );
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(
+                governor.retract_vote(0),
+                Err(GovernorError::VotePeriodEnded)
+            );
+        }
+
+        #[ink::test]
+        fn vote_with_reason_stores_reason_hash() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            let reason_hash = Hash::from([0x07; 32]);
+            assert_eq!(
+                governor.vote_with_reason(0, VoteType::Against, reason_hash),
+                Ok(())
+            );
+            assert_eq!(
+                governor.get_ballot(0, accounts.alice).unwrap().reason_hash,
+                Some(reason_hash)
+            );
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn vote_batch_returns_result_per_ballot() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.cancel(1), Ok(()));
+
+            assert_eq!(
+                governor.vote_batch(vec![
+                    (0, VoteType::For),
+                    (1, VoteType::For),
+                    (2, VoteType::Against),
+                ]),
+                vec![
+                    Ok(()),
+                    Err(GovernorError::ProposalCanceled),
+                    Err(GovernorError::ProposalNotFound),
+                ]
+            );
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 100);
+        }
+
+        #[ink::test]
+        fn quadratic_mode_counts_square_root_of_balance() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(10_000);
+            token::set_balance_of(accounts.alice, 8_100);
+            token::set_balance_of(accounts.bob, 1_000);
+            governor.counting_mode = CountingMode::Quadratic;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.get_proposal(0).unwrap().counting_mode,
+                CountingMode::Quadratic
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 31,
+                    for_votes: 90,
+                    abstain_votes: 0,
+                })
+            );
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+        }
+
+        #[ink::test]
+        fn conviction_multiplies_weight_and_locks_tokens() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let governance_token = AccountId::from(token::GOVERNANCE_TOKEN);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 100);
+            governor.conviction_period = 10;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            set_now(1);
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.vote_with_conviction(0, VoteType::For, Conviction::Locked4x),
+                Ok(())
+            );
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 400);
+            assert_eq!(token::balance_of(governance_token, accounts.bob), 0);
+            assert_eq!(
+                governor.vote_with_conviction(0, VoteType::For, Conviction::Locked1x),
+                Err(GovernorError::ConvictionAlreadyLocked)
+            );
+
+            let unlock_at = ONE_MINUTE + 4 * 10 * ONE_MINUTE;
+            assert_eq!(
+                governor.get_conviction_lock(0, accounts.bob),
+                Some(ConvictionLock {
+                    conviction: Conviction::Locked4x,
+                    amount: 100,
+                    unlock_at,
+                })
+            );
+            set_now(unlock_at - 1);
+            assert_eq!(governor.unlock(0), Err(GovernorError::TokensStillLocked));
+            set_now(unlock_at);
+            assert_eq!(governor.unlock(0), Ok(100));
+            assert_eq!(token::balance_of(governance_token, accounts.bob), 100);
+            assert_eq!(governor.unlock(0), Err(GovernorError::NoConvictionLock));
+        }
+
+        #[ink::test]
+        fn vote_escrow_weight_decays_with_remaining_lock() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let governance_token = AccountId::from(token::GOVERNANCE_TOKEN);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 400);
+            governor.counting_mode = CountingMode::VoteEscrow;
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.lock(100, MAX_VOTE_LOCK + 1),
+                Err(GovernorError::DurationTooLong)
+            );
+            assert_eq!(governor.lock(400, MAX_VOTE_LOCK / 2), Ok(()));
+            assert_eq!(token::balance_of(governance_token, accounts.bob), 0);
+            assert_eq!(governor.vote_escrow_weight(accounts.bob), 200);
+
+            set_now(MAX_VOTE_LOCK / 4 * ONE_MINUTE);
+            assert_eq!(governor.vote_escrow_weight(accounts.bob), 100);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 100);
+            assert_eq!(
+                governor.withdraw_lock(),
+                Err(GovernorError::TokensStillLocked)
+            );
+
+            set_now(MAX_VOTE_LOCK / 2 * ONE_MINUTE);
+            assert_eq!(governor.vote_escrow_weight(accounts.bob), 0);
+            assert_eq!(governor.withdraw_lock(), Ok(400));
+            assert_eq!(token::balance_of(governance_token, accounts.bob), 400);
+        }
+
+        #[ink::test]
+        fn split_vote_divides_weight() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(
+                governor.vote_split(0, 60, 41),
+                Err(GovernorError::SplitExceedsWeight)
+            );
+            assert!(!governor.has_voted(0, accounts.alice));
+            assert_eq!(governor.vote_split(0, 60, 40), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 40,
+                    for_votes: 60,
+                    abstain_votes: 0,
+                })
+            );
+            assert_eq!(governor.vote(0, VoteType::Abstain), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 0,
+                    for_votes: 0,
+                    abstain_votes: 100,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn vote_weight_is_capped_per_account() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 300);
+            governor.max_vote_weight = 20;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.delegate(accounts.charlie), Ok(()));
+            set_sender(accounts.alice);
+            assert_eq!(governor.delegate(accounts.charlie), Ok(()));
+            set_sender(accounts.charlie);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 400);
+        }
+
+        #[ink::test]
+        fn supermajority_threshold_is_enforced() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 350);
+            governor.config.approval_threshold = 66;
+            assert_eq!(governor.approval_threshold(), 66);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Defeated));
+            assert_eq!(
+                governor.queue(0),
+                Err(GovernorError::ApprovalThresholdNotMet)
+            );
+            governor.config.approval_threshold = 60;
+            assert_eq!(governor.queue(0), Ok(()));
+        }
+
+        #[ink::test]
+        fn decided_proposals_can_be_finalized_early() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 400);
+            token::set_balance_of(accounts.bob, 200);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 10, Hash::default()),
+                    Ok(())
+                );
+            }
+            set_now(ONE_MINUTE);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(
+                governor.finalize_early(0),
+                Err(GovernorError::OutcomeNotDecided)
+            );
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            assert_eq!(governor.finalize_early(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(
+                governor.vote(0, VoteType::Against),
+                Err(GovernorError::VotePeriodEnded)
+            );
+            assert_eq!(governor.queue(0), Ok(()));
+
+            set_sender(accounts.alice);
+            governor.config.approval_threshold = 90;
+            assert_eq!(governor.finalize_early(1), Ok(ProposalState::Defeated));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Defeated));
+
+            governor.conviction_period = 1;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 10, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.finalize_early(2),
+                Err(GovernorError::EarlyFinalizationUnsupported)
+            );
+        }
+
+        #[ink::test]
+        fn vote_by_sig_casts_relayed_ballot() {
+            use secp256k1::{
+                Message,
+                PublicKey,
+                Secp256k1,
+                SecretKey,
+            };
+
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let secp = Secp256k1::new();
+            let secret_key = SecretKey::from_slice(&[0x2a; 32]).unwrap();
+            let public_key = PublicKey::from_secret_key(&secp, &secret_key).serialize();
+            let mut voter = [0; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public_key, &mut voter);
+            let voter = AccountId::from(voter);
+            token::set_total_supply(1000);
+            token::set_balance_of(voter, 300);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            let sign = |message_hash: [u8; 32]| {
+                let message = Message::from_slice(&message_hash).unwrap();
+                let (recovery_id, compact) = secp
+                    .sign_ecdsa_recoverable(&message, &secret_key)
+                    .serialize_compact();
+                let mut signature = [0; 65];
+                signature[..64].copy_from_slice(&compact);
+                signature[64] = recovery_id.to_i32() as u8;
+                signature
+            };
+            let signature = sign(governor.ballot_hash(0, VoteType::For, voter, 0));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.vote_by_sig(0, VoteType::Against, voter, signature, 0),
+                Err(GovernorError::InvalidSignature)
+            );
+            assert_eq!(
+                governor.vote_by_sig(0, VoteType::For, voter, signature, 1),
+                Err(GovernorError::InvalidNonce)
+            );
+            assert_eq!(
+                governor.vote_by_sig(0, VoteType::For, voter, signature, 0),
+                Ok(())
+            );
+            assert_eq!(governor.get_nonce(voter), 1);
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 300);
+            assert_eq!(
+                governor.vote_by_sig(0, VoteType::For, voter, signature, 0),
+                Err(GovernorError::InvalidNonce)
+            );
+        }
+
+        fn choice_option(name: &str, to: AccountId, amount: Balance) -> ChoiceOption {
+            ChoiceOption {
+                name: name.into(),
+                payout: Transfer { to, amount },
+            }
+        }
+
+        #[ink::test]
+        fn multi_choice_proposal_pays_the_winner() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 400);
+            token::set_balance_of(accounts.bob, 350);
+            token::set_balance_of(accounts.charlie, 250);
+            let options = vec![
+                choice_option("a", accounts.django, 100),
+                choice_option("b", accounts.eve, 200),
+                choice_option("c", accounts.frank, 300),
+            ];
+            for rule in [WinnerRule::Plurality, WinnerRule::RankedChoice] {
+                let kind = ProposalKind::MultiChoice(MultiChoice {
+                    options: options.clone(),
+                    rule,
+                });
+                assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            }
+
+            assert_eq!(
+                governor.vote(0, VoteType::For),
+                Err(GovernorError::InvalidProposalKind)
+            );
+            assert_eq!(
+                governor.vote_choice(0, vec![3]),
+                Err(GovernorError::InvalidChoice)
+            );
+            assert_eq!(
+                governor.vote_choice(0, vec![1, 1]),
+                Err(GovernorError::InvalidChoice)
+            );
+            for proposal_id in 0..2 {
+                set_sender(accounts.alice);
+                assert_eq!(governor.vote_choice(proposal_id, vec![0]), Ok(()));
+                set_sender(accounts.bob);
+                assert_eq!(governor.vote_choice(proposal_id, vec![1, 0]), Ok(()));
+                set_sender(accounts.charlie);
+                assert_eq!(governor.vote_choice(proposal_id, vec![2, 1]), Ok(()));
+            }
+            assert_eq!(governor.choice_winner(0), Some(0));
+            assert_eq!(governor.choice_winner(1), Some(1));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            let eve_balance = get_balance(accounts.eve);
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(get_balance(accounts.eve), eve_balance + 200);
+        }
+
+        #[ink::test]
+        fn tokens_moved_when_voting_opens_cannot_vote_twice() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let governance_token = AccountId::from(token::GOVERNANCE_TOKEN);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 600);
+
+            set_now(ONE_MINUTE);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(
+                token::transfer(governance_token, accounts.bob, accounts.charlie, 600),
+                Ok(())
+            );
+            set_sender(accounts.charlie);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(
+                token::transfer(governance_token, accounts.charlie, accounts.eve, 600),
+                Ok(())
+            );
+            set_sender(accounts.eve);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 600);
+        }
+
+        #[ink::test]
+        fn receipts_record_each_counted_account() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            token::set_balance_of(accounts.bob, 200);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.delegate(accounts.alice), Ok(()));
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            let now = governor.now();
+            assert_eq!(
+                governor.get_receipt(0, accounts.alice),
+                Some(Receipt {
+                    vote: VoteType::Against,
+                    weight: 100,
+                    timestamp: now,
+                })
+            );
+            assert_eq!(
+                governor.get_receipt(0, accounts.bob),
+                Some(Receipt {
+                    vote: VoteType::Against,
+                    weight: 200,
+                    timestamp: now,
+                })
+            );
+
+            assert_eq!(governor.retract_vote(0), Ok(()));
+            assert_eq!(governor.get_receipt(0, accounts.alice), None);
+            assert_eq!(governor.get_receipt(0, accounts.bob), None);
+        }
+
+        #[ink::test]
+        fn strategy_contract_replaces_token_weight() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 900);
+            let strategy = accounts.eve;
+            strategy::set_total_weight(strategy, 10);
+            strategy::set_weight(strategy, accounts.alice, 2);
+            strategy::set_weight(strategy, accounts.bob, 3);
+            governor.voting_strategy = Some(strategy);
+            assert_eq!(governor.voting_strategy(), Some(strategy));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 2,
+                    for_votes: 3,
+                    abstain_votes: 0,
+                })
+            );
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+        }
+
+        #[ink::test]
+        fn membership_mode_counts_one_vote_per_member() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 900);
+            let collection = accounts.frank;
+            token::set_asset_supply(collection, 4);
+            token::set_asset_balance(collection, accounts.alice, 1);
+            token::set_asset_balance(collection, accounts.bob, 3);
+            governor.counting_mode = CountingMode::Membership(collection);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            set_sender(accounts.charlie);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(
+                governor.get_proposal_vote(0),
+                Some(ProposalVote {
+                    against_votes: 1,
+                    for_votes: 1,
+                    abstain_votes: 0,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn weight_sources_add_to_token_balance() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 100);
+            let staked_lp = accounts.eve;
+            token::set_asset_supply(staked_lp, 1000);
+            token::set_asset_balance(staked_lp, accounts.alice, 200);
+            assert_eq!(
+                Governor::validate_param(&GovernorParam::WeightSources(vec![
+                    WeightSource {
+                        token: staked_lp,
+                        multiplier: 150,
+                    };
+                    MAX_WEIGHT_SOURCES + 1
+                ])),
+                Err(GovernorError::InvalidParameter)
+            );
+            governor.weight_sources = vec![WeightSource {
+                token: staked_lp,
+                multiplier: 150,
+            }];
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.get_proposal_vote(0).unwrap().for_votes, 400);
+            set_now(ONE_MINUTE + 1);
+            // 400 of the 2500 weight of both supplies misses the 50% quorum.
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Defeated));
+        }
+
+        #[ink::test]
+        fn participation_rewards_are_shared_pro_rata() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 100);
+            governor.reward_epoch = 60;
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::FundRewards(RewardFunding {
+                        epoch: 0,
+                        amount: 300,
+                    }),
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.retract_vote(0), Ok(()));
+            assert_eq!(governor.get_participation(0, accounts.alice), 2);
+            assert_eq!(governor.get_participation(0, accounts.bob), 1);
+
+            set_sender(accounts.alice);
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            let treasury = governor.treasury_balance();
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.get_reward_pool(0), 300);
+            assert_eq!(governor.treasury_balance(), treasury - 300);
+            assert_eq!(
+                governor.claim_rewards(0),
+                Err(GovernorError::EpochNotSettled)
+            );
+
+            set_now((60 + MAX_DURATION) * ONE_MINUTE + 1);
+            let alice_balance = get_balance(accounts.alice);
+            assert_eq!(governor.claim_rewards(0), Ok(200));
+            assert_eq!(get_balance(accounts.alice), alice_balance + 200);
+            assert_eq!(
+                governor.claim_rewards(0),
+                Err(GovernorError::NothingToClaim)
+            );
+            set_sender(accounts.bob);
+            assert_eq!(governor.claim_rewards(0), Ok(100));
+            assert_eq!(governor.reserved_rewards, 0);
+        }
+
+        #[ink::test]
+        fn quorum_is_in_basis_points() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            governor.config.quorum = 1_250;
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 124);
+            token::set_balance_of(accounts.bob, 125);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Defeated));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Succeeded));
+        }
+
+        #[ink::test]
+        fn quorum_decays_to_floor_while_open() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            governor.config.quorum_floor = 1_000;
+            governor.config.quorum_decay = MAX_DURATION;
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 200);
+            assert_eq!(
+                governor.propose(accounts.django, 100, MAX_DURATION, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+
+            assert_eq!(governor.current_quorum(0), Some(5_000));
+            set_now(MAX_DURATION / 2 * ONE_MINUTE);
+            assert_eq!(governor.current_quorum(0), Some(3_000));
+            set_now(MAX_DURATION * ONE_MINUTE + 1);
+            assert_eq!(governor.current_quorum(0), Some(1_000));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.current_quorum(1), Some(4_934));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Defeated));
+        }
+
+        #[ink::test]
+        fn join_mints_shares_for_deposit() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            set_sender(accounts.bob);
+            set_value_transferred(5);
+            assert_eq!(governor.join(), Err(GovernorError::JoiningDisabled));
+
+            governor.join_rate = 10;
+            assert_eq!(governor.join(), Ok(50));
+            assert_eq!(
+                token::balance_of(governor.governance_token, accounts.bob),
+                50
+            );
+            assert_eq!(governor.total_supply(), Ok(1050));
+            set_value_transferred(0);
+            assert_eq!(governor.join(), Err(GovernorError::AmountShouldNotBeZero));
+        }
+
+        #[ink::test]
+        fn rage_quit_redeems_treasury_share() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 100);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::Against), Ok(()));
+            assert_eq!(governor.rage_quit(50), Err(GovernorError::VotesPending));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(
+                governor.rage_quit(200),
+                Err(GovernorError::InsufficientTokenBalance)
+            );
+            let bob_balance = get_balance(accounts.bob);
+            assert_eq!(governor.rage_quit(50), Ok(50));
+            assert_eq!(get_balance(accounts.bob), bob_balance + 50);
+            assert_eq!(get_balance(contract_id()), 950);
+            assert_eq!(governor.balance_of(accounts.bob), Ok(50));
+            assert_eq!(governor.total_supply(), Ok(950));
+        }
+
+        #[ink::test]
+        fn asset_registry_is_admin_managed() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let asset = accounts.eve;
+            assert_eq!(governor.add_asset(asset), Ok(()));
+            assert_eq!(
+                governor.add_asset(asset),
+                Err(GovernorError::AssetAlreadyRegistered)
+            );
+            assert_eq!(
+                governor.add_asset(governor.governance_token),
+                Err(GovernorError::InvalidParameter)
+            );
+            assert_eq!(governor.get_assets(), vec![asset]);
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.add_asset(accounts.frank),
+                Err(GovernorError::Unauthorized)
+            );
+            assert_eq!(
+                governor.remove_asset(asset),
+                Err(GovernorError::Unauthorized)
+            );
+            set_sender(accounts.alice);
+            assert_eq!(governor.remove_asset(asset), Ok(()));
+            assert_eq!(
+                governor.remove_asset(asset),
+                Err(GovernorError::AssetNotRegistered)
+            );
+            assert!(governor.get_assets().is_empty());
+        }
+
+        #[ink::test]
+        fn rage_quit_pays_registered_assets() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let asset = accounts.eve;
+            assert_eq!(governor.add_asset(asset), Ok(()));
+            token::set_asset_balance(asset, contract_id(), 400);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 100);
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.rage_quit(100), Ok(100));
+            assert_eq!(token::balance_of(asset, accounts.bob), 40);
+            assert_eq!(token::balance_of(asset, contract_id()), 360);
+        }
+
+        #[ink::test]
+        fn treasury_balances_cover_registered_assets() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let asset = accounts.eve;
+            token::set_asset_balance(asset, contract_id(), 400);
+            token::set_balance_of(contract_id(), 300);
+            governor.locked_tokens = 100;
+
+            assert_eq!(governor.treasury_balance(), 1000);
+            assert_eq!(
+                governor.treasury_balance_of(asset),
+                Err(GovernorError::AssetNotRegistered)
+            );
+            assert_eq!(governor.add_asset(asset), Ok(()));
+            assert_eq!(governor.treasury_balance_of(asset), Ok(400));
+            assert_eq!(
+                governor.treasury_balance_of(governor.governance_token),
+                Ok(200)
+            );
+        }
+
+        #[ink::test]
+        fn dividends_are_claimed_pro_rata() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 300);
+            token::set_balance_of(accounts.charlie, 700);
+            set_sender(accounts.charlie);
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Distribute(100), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.treasury_balance(), 900);
+            assert_eq!(
+                governor.claim_dividend(1),
+                Err(GovernorError::DistributionNotFound)
+            );
+
+            assert_eq!(governor.claim_dividend(0), Ok(70));
+            assert_eq!(
+                governor.claim_dividend(0),
+                Err(GovernorError::DividendAlreadyClaimed)
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                token::transfer(
+                    governor.governance_token,
+                    accounts.bob,
+                    accounts.django,
+                    300
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.claim_dividend(0), Ok(30));
+            set_sender(accounts.django);
+            assert_eq!(
+                governor.claim_dividend(0),
+                Err(GovernorError::NothingToClaim)
+            );
+            assert_eq!(governor.treasury_balance(), 900);
+            assert_eq!(get_balance(contract_id()), 900);
+        }
+
+        #[ink::test]
+        fn distribution_uses_proposal_snapshot() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 300);
+            token::set_balance_of(accounts.charlie, 700);
+            set_now(ONE_MINUTE);
+            set_sender(accounts.charlie);
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Distribute(100), 1, Hash::default()),
+                Ok(())
+            );
+            // Bought after the announcement: no share for django.
+            assert_eq!(
+                token::transfer(
+                    governor.governance_token,
+                    accounts.bob,
+                    accounts.django,
+                    300
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(2 * ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(2 * ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            let distribution = governor.get_distribution(0).unwrap();
+            assert_eq!(distribution.proposal_id, 0);
+            assert_eq!(distribution.snapshot, ONE_MINUTE - 1);
+            assert_eq!(governor.claimable(accounts.bob, 0), 30);
+            assert_eq!(governor.claimable(accounts.django, 0), 0);
+            assert_eq!(governor.claimable(accounts.bob, 1), 0);
+            set_sender(accounts.bob);
+            assert_eq!(governor.claim_dividend(0), Ok(30));
+            assert_eq!(governor.claimable(accounts.bob, 0), 0);
+        }
+
+        #[ink::test]
+        fn returns_are_attributed_to_investments() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 200, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_value_transferred(50);
+            assert_eq!(
+                governor.record_return(0),
+                Err(GovernorError::NotAnInvestment)
+            );
+
+            set_value_transferred(0);
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.investment_roi(0), Some(-10_000));
+
+            set_sender(accounts.django);
+            set_value_transferred(250);
+            assert_eq!(governor.record_return(0), Ok(()));
+            assert_eq!(
+                governor.get_investment(0),
+                Some(Investment {
+                    disbursed: 200,
+                    returned: 250,
+                })
+            );
+            assert_eq!(governor.investment_roi(0), Some(2_500));
+            assert_eq!(governor.get_portfolio().roi(), Some(2_500));
+            assert_eq!(governor.investment_roi(1), None);
+        }
+
+        #[ink::test]
+        fn stream_vests_linearly_until_canceled() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let stream = StreamPayout {
+                to: accounts.django,
+                amount: 100,
+                duration: 10,
+            };
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Stream(stream), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(
+                governor.propose_kind(ProposalKind::CancelStream(0), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+
+            set_sender(accounts.django);
+            assert_eq!(
+                governor.withdraw_stream(0),
+                Err(GovernorError::NothingToClaim)
+            );
+            set_now(start + 3 * ONE_MINUTE);
+            assert_eq!(governor.withdraw_stream(0), Ok(30));
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.withdraw_stream(0),
+                Err(GovernorError::NotRecipient)
+            );
+
+            set_sender(accounts.alice);
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(start + (3 + TIMELOCK_DELAY) * ONE_MINUTE);
+            assert_eq!(governor.execute(1), Ok(()));
+            set_sender(accounts.django);
+            set_now(start + 20 * ONE_MINUTE);
+            assert_eq!(governor.withdraw_stream(0), Ok(50));
+            assert_eq!(
+                governor.withdraw_stream(0),
+                Err(GovernorError::NothingToClaim)
+            );
+            assert_eq!(governor.get_investment(0).unwrap().disbursed, 80);
+        }
+
+        #[ink::test]
+        fn payroll_pays_salary_per_period() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let salary = RecurringPayout {
+                to: accounts.django,
+                amount: 10,
+                period: 30,
+                periods: 12,
+            };
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::Hire(salary.clone()),
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Hire(salary), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.execute(1), Err(GovernorError::AlreadyOnPayroll));
+            assert_eq!(governor.get_salary(accounts.django), Some(0));
+            assert_eq!(governor.claim_salary(), Err(GovernorError::NotOnPayroll));
+
+            set_sender(accounts.django);
+            assert_eq!(governor.claim_salary(), Ok(10));
+            assert_eq!(governor.claim_salary(), Err(GovernorError::NothingToClaim));
+            set_now(start + 60 * ONE_MINUTE);
+
+            set_sender(accounts.alice);
+            assert_eq!(
+                governor.propose_kind(
+                    ProposalKind::Dismiss(accounts.django),
+                    1,
+                    Hash::default()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.vote(2, VoteType::For), Ok(()));
+            set_now(start + 61 * ONE_MINUTE + 1);
+            assert_eq!(governor.queue(2), Ok(()));
+            set_now(start + (61 + TIMELOCK_DELAY) * ONE_MINUTE + 1);
+            let django_balance = get_balance(accounts.django);
+            assert_eq!(governor.execute(2), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance + 20);
+            assert_eq!(governor.get_salary(accounts.django), None);
+            assert_eq!(governor.get_investment(0).unwrap().disbursed, 30);
+            set_now(start + 120 * ONE_MINUTE);
+            set_sender(accounts.django);
+            assert_eq!(governor.claim_salary(), Err(GovernorError::NotOnPayroll));
+        }
+
+        #[ink::test]
+        fn execute_respects_epoch_budget() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.budget_epoch = 10;
+            governor.set_param(&GovernorParam::BudgetCap(150));
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.remaining_budget(), Some(50));
+            assert_eq!(governor.execute(1), Err(GovernorError::BudgetExceeded));
+
+            set_now(10 * ONE_MINUTE);
+            assert_eq!(governor.remaining_budget(), Some(150));
+            governor.budget_carry_over = CarryOver::UpTo(20);
+            assert_eq!(governor.remaining_budget(), Some(170));
+            governor.budget_carry_over = CarryOver::Full;
+            assert_eq!(governor.remaining_budget(), Some(200));
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(governor.remaining_budget(), Some(100));
+        }
+
+        #[ink::test]
+        fn escrow_is_released_or_refunded_by_vote() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 400);
+            let transfer = Transfer {
+                to: accounts.django,
+                amount: 100,
+            };
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Escrow(transfer), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            let executed_at = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(executed_at);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.treasury_balance(), 900);
+            assert_eq!(get_balance(contract_id()), 1000);
+
+            // A failed release vote refunds the escrow.
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose_kind(
+                        ProposalKind::ReleaseEscrow(0),
+                        1,
+                        Hash::default()
+                    ),
+                    Ok(())
+                );
+            }
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(2, VoteType::For), Ok(()));
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            assert_eq!(governor.vote(2, VoteType::For), Ok(()));
+            assert_eq!(
+                governor.refund_escrow(0, 1),
+                Err(GovernorError::ReleaseNotDefeated)
+            );
+            set_now(executed_at + ONE_MINUTE + 1);
+            assert_eq!(
+                governor.refund_escrow(0, 2),
+                Err(GovernorError::ReleaseNotDefeated)
+            );
+            assert_eq!(
+                governor.refund_escrow(0, 0),
+                Err(GovernorError::InvalidProposalKind)
+            );
+            assert_eq!(governor.refund_escrow(0, 1), Ok(()));
+            assert_eq!(governor.treasury_balance(), 1000);
+            assert_eq!(
+                governor.refund_escrow(0, 1),
+                Err(GovernorError::EscrowSettled)
+            );
+            assert_eq!(governor.queue(2), Ok(()));
+            set_now(executed_at + (1 + TIMELOCK_DELAY) * ONE_MINUTE + 1);
+            assert_eq!(governor.execute(2), Err(GovernorError::EscrowSettled));
+        }
+
+        #[ink::test]
+        fn swap_proposal_trades_on_router_within_slippage() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let (asset_in, asset_out) = (accounts.eve, accounts.frank);
+            token::set_asset_balance(asset_in, contract_id(), 500);
+            dex::set_rate(90);
+            for min_amount_out in [200, 180] {
+                let swap = Swap {
+                    asset_in: Some(asset_in),
+                    asset_out: Some(asset_out),
+                    amount_in: 200,
+                    min_amount_out,
+                };
+                assert_eq!(
+                    governor.propose_kind(ProposalKind::Swap(swap), 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(1), Err(GovernorError::NoDexRouter));
+
+            governor.dex_router = Some(accounts.django);
+            assert_eq!(governor.execute(0), Err(GovernorError::SlippageExceeded));
+            token::set_asset_balance(asset_in, contract_id(), 500);
+            token::set_asset_balance(asset_out, contract_id(), 0);
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(token::balance_of(asset_in, contract_id()), 300);
+            assert_eq!(token::balance_of(asset_out, contract_id()), 180);
+        }
+
+        #[ink::test]
+        fn stake_and_unstake_account_rewards() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.staking_adapter = Some(accounts.django);
+            staking::set_reward(10);
+            for kind in [
+                ProposalKind::Stake(400),
+                ProposalKind::Unstake(500),
+                ProposalKind::Unstake(300),
+            ] {
+                assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            }
+            for proposal_id in 0..3 {
+                assert_eq!(governor.vote(proposal_id, VoteType::For), Ok(()));
+            }
+            set_now(ONE_MINUTE + 1);
+            for proposal_id in 0..3 {
+                assert_eq!(governor.queue(proposal_id), Ok(()));
+            }
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.treasury_balance(), 600);
+            assert_eq!(governor.staking(), (400, 0));
+
+            assert_eq!(governor.execute(1), Err(GovernorError::InsufficientStake));
+            assert_eq!(governor.execute(2), Ok(()));
+            assert_eq!(governor.treasury_balance(), 930);
+            assert_eq!(governor.staking(), (100, 30));
+        }
+
+        #[ink::test]
+        fn buyback_burns_bought_and_contributed_tokens() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.dex_router = Some(accounts.django);
+            dex::set_rate(200);
+            let buyback = Buyback {
+                amount: 100,
+                min_tokens_out: 150,
+                contributed: 50,
+            };
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Buyback(buyback), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.treasury_balance(), 900);
+            // The test contract holds alice's tokens: 600 + 200 bought - 250 burnt.
+            assert_eq!(
+                token::balance_of(token::GOVERNANCE_TOKEN.into(), contract_id()),
+                550
+            );
+            assert_eq!(token::total_supply(token::GOVERNANCE_TOKEN.into()), 750);
+        }
+
+        #[ink::test]
+        fn donate_records_donors() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            assert_eq!(governor.donate(), Err(GovernorError::AmountShouldNotBeZero));
+
+            for (donor, amount, now) in [
+                (accounts.bob, 100, 1),
+                (accounts.charlie, 50, 2),
+                (accounts.bob, 20, 3),
+            ] {
+                set_sender(donor);
+                set_now(now);
+                set_value_transferred(amount);
+                assert_eq!(governor.donate(), Ok(()));
+            }
+            assert_eq!(governor.donor_count(), 2);
+            assert_eq!(
+                governor.get_donors(0, 10),
+                vec![accounts.bob, accounts.charlie]
+            );
+            assert_eq!(governor.get_donors(1, 10), vec![accounts.charlie]);
+            assert_eq!(
+                governor.get_donation(accounts.bob),
+                Some(Donation {
+                    total: 120,
+                    count: 2,
+                    last_at: 3
+                })
+            );
+            assert_eq!(governor.get_donation(accounts.django), None);
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn contributions_mint_tiered_receipts() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let receipt_nft = accounts.eve;
+            assert_eq!(
+                Governor::validate_param(&GovernorParam::ReceiptTiers(vec![100, 100])),
+                Err(GovernorError::InvalidParameter)
+            );
+            governor.receipt_nft = Some(receipt_nft);
+            governor.receipt_tiers = vec![100, 500];
+
+            for amount in [50, 100, 700] {
+                set_sender(accounts.bob);
+                set_value_transferred(amount);
+                assert_eq!(governor.donate(), Ok(()));
+            }
+            assert_eq!(token::balance_of(receipt_nft, accounts.bob), 2);
+            assert_eq!(
+                governor.get_contribution_receipt(1),
+                Some(ContributionReceipt {
+                    contributor: accounts.bob,
+                    amount: 700,
+                    tier: 2
+                })
+            );
+            assert_eq!(governor.get_contribution_receipt(2), None);
+        }
+
+        #[ink::test]
+        fn fundraising_round_closes_at_cap_or_deadline() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let terms = RoundTerms {
+                soft_cap: 100,
+                hard_cap: 300,
+                rate: 2,
+                duration: 30,
+            };
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose_kind(
+                        ProposalKind::OpenRound(terms),
+                        1,
+                        Hash::default()
+                    ),
+                    Ok(())
+                );
+                assert_eq!(
+                    governor.vote(governor.next_proposal_id - 1, VoteType::For),
+                    Ok(())
+                );
+            }
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.execute(1), Ok(()));
+
+            set_sender(accounts.bob);
+            set_value_transferred(250);
+            assert_eq!(governor.contribute(0), Ok(500));
+            set_value_transferred(100);
+            assert_eq!(governor.contribute(0), Err(GovernorError::HardCapExceeded));
+            set_value_transferred(50);
+            assert_eq!(governor.contribute(0), Ok(100));
+            assert_eq!(governor.contribute(0), Err(GovernorError::RoundClosed));
+            assert_eq!(
+                token::balance_of(token::GOVERNANCE_TOKEN.into(), accounts.bob),
+                600
+            );
+            assert_eq!(
+                governor.get_round_contribution(0, accounts.bob),
+                RoundContribution {
+                    deposited: 300,
+                    minted: 600
+                }
+            );
+
+            set_now(start + 31 * ONE_MINUTE);
+            assert_eq!(governor.contribute(1), Err(GovernorError::RoundClosed));
+            assert_eq!(governor.contribute(2), Err(GovernorError::RoundNotFound));
+        }
+
+        #[ink::test]
+        fn refund_failed_round() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let terms = RoundTerms {
+                soft_cap: 200,
+                hard_cap: 300,
+                rate: 2,
+                duration: 30,
+            };
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose_kind(
+                        ProposalKind::OpenRound(terms),
+                        1,
+                        Hash::default()
+                    ),
+                    Ok(())
+                );
+                assert_eq!(
+                    governor.vote(governor.next_proposal_id - 1, VoteType::For),
+                    Ok(())
+                );
+            }
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.execute(1), Ok(()));
+
+            set_sender(accounts.bob);
+            for (round_id, amount) in [(0, 150), (1, 120), (1, 120), (1, 10)] {
+                set_value_transferred(amount);
+                set_balance(contract_id(), get_balance(contract_id()) + amount);
+                assert!(governor.contribute(round_id).is_ok());
+            }
+            assert_eq!(governor.treasury_balance(), 1250);
+            assert_eq!(governor.refund(0), Err(GovernorError::RoundNotFailed));
+
+            set_now(start + 31 * ONE_MINUTE);
+            assert_eq!(governor.refund(1), Err(GovernorError::RoundNotFailed));
+            assert_eq!(governor.refund(0), Ok(150));
+            assert_eq!(governor.refund(0), Err(GovernorError::NothingToRefund));
+            assert_eq!(
+                token::balance_of(token::GOVERNANCE_TOKEN.into(), accounts.bob),
+                500
+            );
+            assert_eq!(governor.treasury_balance(), 1250);
+            assert_eq!(get_balance(contract_id()), 1250);
+        }
+
+        #[ink::test]
+        fn large_payouts_need_supermajority() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 300);
+            token::set_balance_of(accounts.bob, 100);
+            governor.large_payout = Some(LargePayout {
+                threshold: PayoutThreshold::Treasury(2_000),
+                approval_threshold: 75,
+                quorum: 3_000,
+            });
+            assert_eq!(
+                governor.propose(accounts.django, 200, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 201, 1, Hash::default()),
+                Ok(())
+            );
+            assert!(!governor.get_proposal(0).unwrap().large_payout);
+            assert!(governor.get_proposal(1).unwrap().large_payout);
+            governor.config.quorum = 2_000;
+            for proposal_id in 0..2 {
+                set_sender(accounts.alice);
+                assert_eq!(governor.vote(proposal_id, VoteType::For), Ok(()));
+                set_sender(accounts.bob);
+                assert_eq!(governor.vote(proposal_id, VoteType::Against), Ok(()));
+            }
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.current_quorum(1), Some(3_000));
+            // 300 of 400 cast votes is exactly the 75% required.
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Succeeded));
+
+            governor.large_payout = Some(LargePayout {
+                threshold: PayoutThreshold::Absolute(150),
+                approval_threshold: 80,
+                quorum: 0,
+            });
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Defeated));
+        }
+
+        #[ink::test]
+        fn oracle_values_treasury_and_payouts() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let asset = accounts.eve;
+            token::set_asset_balance(asset, contract_id(), 50);
+            governor.assets = vec![asset];
+            assert_eq!(governor.total_aum(), None);
+
+            governor.price_oracle = Some(accounts.frank);
+            oracle::set_price(None, 2 * PRICE_SCALE);
+            oracle::set_price(Some(governor.governance_token), PRICE_SCALE / 2);
+            assert_eq!(governor.total_aum(), None);
+            oracle::set_price(Some(asset), 4 * PRICE_SCALE);
+            // 1000 native at 2, alice's 600 tokens held by the test contract at
+            // 0.5 and 50 of the asset at 4.
+            assert_eq!(governor.total_aum(), Some(2500));
+
+            governor.large_payout = Some(LargePayout {
+                threshold: PayoutThreshold::Absolute(300),
+                approval_threshold: 80,
+                quorum: 0,
+            });
+            let transfer = |amount| {
+                ProposalKind::TokenTransfer(vec![TokenTransfer {
+                    asset,
+                    to: accounts.django,
+                    amount,
+                }])
+            };
+            for kind in [transfer(75), transfer(76), ProposalKind::Stake(151)] {
+                assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            }
+            let large = |governor: &Governor, proposal_id| {
+                governor.get_proposal(proposal_id).unwrap().large_payout
+            };
+            assert!(!large(&governor, 0));
+            assert!(large(&governor, 1));
+            assert!(large(&governor, 2));
+        }
+
+        #[ink::test]
+        fn large_executions_need_cosigners() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let co_signing = CoSigning {
+                signers: vec![accounts.bob, accounts.charlie, accounts.eve],
+                required: 2,
+                threshold: 100,
+            };
+            assert_eq!(
+                Governor::validate_param(&GovernorParam::CoSigning(Some(CoSigning {
+                    signers: vec![accounts.bob, accounts.bob],
+                    ..co_signing.clone()
+                }))),
+                Err(GovernorError::InvalidParameter)
+            );
+            governor.co_signing = Some(co_signing);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 101, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.execute(1), Err(GovernorError::SignaturesMissing));
+
+            assert_eq!(governor.cosign(1), Err(GovernorError::NotSigner));
+            set_sender(accounts.bob);
+            assert_eq!(governor.cosign(1), Ok(()));
+            assert_eq!(governor.cosign(1), Err(GovernorError::AlreadySigned));
+            assert_eq!(governor.execute(1), Err(GovernorError::SignaturesMissing));
+            set_sender(accounts.eve);
+            assert_eq!(governor.cosign(1), Ok(()));
+            assert_eq!(governor.signatures(1), 2);
+            assert_eq!(governor.execute(1), Ok(()));
+        }
+
+        #[ink::test]
+        fn vesting_grants_unlock_after_cliff() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let grant = |amount, cliff| {
+                ProposalKind::Vesting(VestingGrant {
+                    to: accounts.django,
+                    amount,
+                    cliff,
+                    duration: 10,
+                })
+            };
+            assert_eq!(
+                governor.propose_kind(grant(100, 11), 1, Hash::default()),
+                Err(GovernorError::InvalidSchedule)
+            );
+            for kind in [grant(100, 4), grant(50, 0)] {
+                assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            }
+            for proposal_id in 0..2 {
+                assert_eq!(governor.vote(proposal_id, VoteType::For), Ok(()));
+            }
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(governor.get_vesting_grants(accounts.django), vec![0, 1]);
+
+            set_sender(accounts.django);
+            set_now(start + 2 * ONE_MINUTE);
+            assert_eq!(governor.claim_vested(), Ok(10));
+            set_now(start + 4 * ONE_MINUTE);
+            assert_eq!(governor.claim_vested(), Ok(40 + 10));
+            set_now(start + 10 * ONE_MINUTE);
+            assert_eq!(governor.claim_vested(), Ok(60 + 30));
+            assert_eq!(governor.claim_vested(), Err(GovernorError::NothingToClaim));
+            assert!(governor.get_vesting_grants(accounts.django).is_empty());
+            assert_eq!(governor.treasury_balance(), 850);
+        }
+
+        #[ink::test]
+        fn clawback_stops_vesting_grant() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let grant = VestingGrant {
+                to: accounts.django,
+                amount: 100,
+                cliff: 0,
+                duration: 20,
+            };
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Vesting(grant), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            let start = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(start);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            for kind in [ProposalKind::Clawback(0), ProposalKind::Clawback(7)] {
+                assert_eq!(governor.propose_kind(kind, 1, Hash::default()), Ok(()));
+            }
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(2, VoteType::For), Ok(()));
+            set_now(start + ONE_MINUTE + 1);
+            assert_eq!(governor.queue(1), Ok(()));
+            assert_eq!(governor.queue(2), Ok(()));
+            let now = start + (2 + TIMELOCK_DELAY) * ONE_MINUTE;
+            set_now(now);
+            assert_eq!(governor.execute(2), Err(GovernorError::GrantNotFound));
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(governor.get_vesting(0).unwrap().amount, 35);
+
+            set_sender(accounts.django);
+            set_now(now + 10 * ONE_MINUTE);
+            assert_eq!(governor.claim_vested(), Ok(35));
+            assert!(governor.get_vesting_grants(accounts.django).is_empty());
+            assert_eq!(governor.treasury_balance(), 965);
+        }
+
+        #[ink::test]
+        fn ledger_records_treasury_movements() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            set_sender(accounts.bob);
+            set_now(5);
+            set_value_transferred(40);
+            assert_eq!(governor.donate(), Ok(()));
+            set_value_transferred(0);
+            set_sender(accounts.alice);
+            set_now(0);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            let now = ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE;
+            set_now(now);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            assert_eq!(governor.ledger_len(), 2);
+            assert_eq!(
+                governor.get_ledger(0, 10),
+                vec![
+                    LedgerEntry {
+                        timestamp: 5,
+                        movement: Movement::Donation,
+                        inflow: true,
+                        counterparty: accounts.bob,
+                        asset: None,
+                        amount: 40,
+                        proposal_id: None,
+                    },
+                    LedgerEntry {
+                        timestamp: now,
+                        movement: Movement::Payout,
+                        inflow: false,
+                        counterparty: accounts.django,
+                        asset: None,
+                        amount: 100,
+                        proposal_id: Some(0),
+                    },
+                ]
+            );
+            assert_eq!(governor.get_ledger(1, 1).len(), 1);
+            assert!(governor.get_ledger(2, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn payouts_pay_fee_to_reserve() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                Governor::validate_param(&GovernorParam::PayoutFee(10_001)),
+                Err(GovernorError::InvalidParameter)
+            );
+            governor.payout_fee = 250;
+            let django_balance = get_balance(accounts.django);
+            let eve_balance = get_balance(accounts.eve);
+            assert_eq!(
+                governor.propose(accounts.django, 200, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            governor.fee_reserve = Some(accounts.eve);
+            assert_eq!(governor.execute(0), Ok(()));
+
+            assert_eq!(get_balance(accounts.django), django_balance + 195);
+            assert_eq!(get_balance(accounts.eve), eve_balance + 5);
+            assert_eq!(governor.treasury_balance(), 800);
+            assert_eq!(governor.get_ledger(0, 1)[0].movement, Movement::Fee);
+        }
+
+        #[ink::test]
+        fn guardian_vetoes_passed_proposals() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.guardian = Some(accounts.eve);
+            governor.guardian_window = 10;
+            for _ in 0..3 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_sender(accounts.eve);
+            assert_eq!(governor.veto(0), Err(GovernorError::VotePeriodNotEnded));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.veto(2), Err(GovernorError::QuorumNotReached));
+            assert_eq!(governor.veto(0), Ok(()));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Canceled));
+            set_sender(accounts.alice);
+            assert_eq!(governor.veto(1), Err(GovernorError::Unauthorized));
+
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(12 * ONE_MINUTE);
+            set_sender(accounts.eve);
+            assert_eq!(governor.veto(1), Err(GovernorError::VetoWindowClosed));
+        }
+
+        #[ink::test]
+        fn pause_blocks_propose_vote_and_execute() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.guardian = Some(accounts.eve);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.pause(), Err(GovernorError::Unauthorized));
+            set_sender(accounts.eve);
+            assert_eq!(governor.pause(), Ok(()));
+            set_sender(accounts.alice);
+            assert!(governor.paused());
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::Paused)
+            );
+            assert_eq!(governor.vote(1, VoteType::For), Err(GovernorError::Paused));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Err(GovernorError::Paused));
+
+            assert_eq!(governor.unpause(), Ok(()));
+            assert_eq!(governor.execute(0), Ok(()));
+        }
+
+        #[ink::test]
+        fn roles_restrict_messages_once_granted() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.grant_role(Role::Proposer, accounts.bob), Ok(()));
+            assert_eq!(
+                governor.grant_role(Role::Treasurer, accounts.charlie),
+                Ok(())
+            );
+            assert_eq!(governor.grant_role(Role::Executor, accounts.eve), Ok(()));
+            assert!(governor.has_role(Role::Proposer, accounts.bob));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::MissingRole)
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Err(GovernorError::MissingRole));
+
+            set_sender(accounts.charlie);
+            assert_eq!(governor.add_asset(accounts.frank), Ok(()));
+            assert_eq!(
+                governor.grant_role(Role::Executor, accounts.charlie),
+                Err(GovernorError::Unauthorized)
+            );
+            assert_eq!(governor.renounce_role(Role::Treasurer), Ok(()));
+            assert_eq!(
+                governor.remove_asset(accounts.frank),
+                Err(GovernorError::Unauthorized)
+            );
+
+            set_sender(accounts.eve);
+            assert_eq!(governor.execute(0), Ok(()));
+            set_sender(accounts.alice);
+            assert_eq!(governor.revoke_role(Role::Proposer, accounts.bob), Ok(()));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn admin_transfer_needs_acceptance() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.transfer_admin(Some(accounts.bob)),
+                Err(GovernorError::Unauthorized)
+            );
+            set_sender(accounts.alice);
+            assert_eq!(governor.transfer_admin(Some(accounts.charlie)), Ok(()));
+            assert_eq!(governor.admin(), accounts.alice);
+            set_sender(accounts.bob);
+            assert_eq!(governor.accept_admin(), Err(GovernorError::NotPendingAdmin));
+            set_sender(accounts.charlie);
+            assert_eq!(governor.accept_admin(), Ok(()));
+            assert_eq!(governor.admin(), accounts.charlie);
+            assert_eq!(governor.pending_admin(), None);
+            assert_eq!(governor.accept_admin(), Err(GovernorError::NotPendingAdmin));
+            assert_eq!(governor.pause(), Ok(()));
+        }
+
+        #[ink::test]
+        fn reentrant_calls_are_refused() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let call = ContractCall {
+                callee: AccountId::from([0x0b; 32]),
+                selector: [0xde, 0xad, 0xbe, 0xef],
+                input: Vec::new(),
+                transferred_value: 0,
+            };
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose_call(call.clone(), 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+
+            // The callee reads the flag from storage, as a callback would.
+            let key = governor.entered.key();
+            calls::watch(key);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(calls::observed(), vec![Some(true)]);
+            assert_eq!(
+                ink::env::get_contract_storage::<Key, bool>(&key),
+                Ok(Some(false))
+            );
+
+            // What a malicious callee sees when calling back mid-execution.
+            ink::env::set_contract_storage(&key, &true);
+            set_sender(accounts.django);
+            assert_eq!(governor.execute(0), Err(GovernorError::ReentrantCall));
+            assert_eq!(governor.rage_quit(1), Err(GovernorError::ReentrantCall));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::ReentrantCall)
+            );
+            assert_eq!(governor.claim_vested(), Err(GovernorError::ReentrantCall));
+            set_value_transferred(10);
+            assert_eq!(governor.donate(), Err(GovernorError::ReentrantCall));
+            set_value_transferred(0);
+
+            ink::env::set_contract_storage(&key, &false);
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(
+                governor.execute(1),
+                Err(GovernorError::ProposalAlreadyExecuted)
+            );
+            assert_eq!(
+                ink::env::get_contract_storage::<Key, bool>(&key),
+                Ok(Some(false))
+            );
+        }
+
+        #[ink::test]
+        fn overflowing_inputs_are_rejected() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.config.max_duration = u64::MAX;
+            assert_eq!(
+                governor.propose(
+                    accounts.django,
+                    100,
+                    u64::MAX / ONE_MINUTE + 1,
+                    Hash::default()
+                ),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+            governor.next_proposal_id = ProposalId::MAX;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+
+            let mut proposal_vote = ProposalVote {
+                for_votes: Balance::MAX,
+                ..Default::default()
+            };
+            assert_eq!(
+                proposal_vote.add(VoteType::For, 1),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+            assert_eq!(
+                proposal_vote.remove(VoteType::Against, 1),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+            assert_eq!(proposal_vote.remove(VoteType::For, 1), Ok(()));
+        }
+
+        #[ink::test]
+        fn failed_transfer_leaves_proposal_executable() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+
+            calls::reject_transfers(accounts.django, true);
+            assert_eq!(governor.execute(0), Err(GovernorError::TransferError));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Queued));
+            calls::reject_transfers(accounts.django, false);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Executed));
+        }
+
+        #[ink::test]
+        fn unreachable_token_fails_vote() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+
+            token::set_broken(governor.governance_token);
+            assert_eq!(
+                governor.vote(0, VoteType::For),
+                Err(GovernorError::CrossContractCallFailed)
+            );
+            assert!(!governor.has_voted(0, accounts.alice));
+        }
+
+        #[ink::test]
+        fn call_gas_limit_is_a_param() {
+            let mut governor = create_contract(1000);
+            assert_eq!(governor.call_gas_limit(), 0);
+            governor.set_param(&GovernorParam::CallGasLimit(5_000_000_000));
+            assert_eq!(governor.call_gas_limit(), 5_000_000_000);
+        }
+
+        #[ink::test]
+        fn execute_pays_only_once() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+
+            calls::reject_transfers(accounts.django, true);
+            assert_eq!(governor.execute(0), Err(GovernorError::TransferError));
+            assert!(!governor.get_proposal(0).unwrap().executed);
+            calls::reject_transfers(accounts.django, false);
+
+            let django_balance = get_balance(accounts.django);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert!(governor.get_proposal(0).unwrap().executed);
+            assert_eq!(
+                governor.execute(0),
+                Err(GovernorError::ProposalAlreadyExecuted)
+            );
+            assert_eq!(get_balance(accounts.django), django_balance + 100);
+        }
+
         #[ink::test]
-        fn propose_works() {
+        fn update_config_is_self_gated() {
             let accounts = default_accounts();
             let mut governor = create_contract(1000);
+            let config = GovernanceConfig {
+                quorum: 2_000,
+                timelock_delay: TIMELOCK_DELAY + 5,
+                ..governor.get_config()
+            };
+            set_sender(accounts.bob);
             assert_eq!(
-                governor.propose(accounts.django, 0, 1),
-                Err(GovernorError::AmountShouldNotBeZero)
+                governor.update_config(config),
+                Err(GovernorError::Unauthorized)
             );
             assert_eq!(
-                governor.propose(accounts.django, 100, 0),
-                Err(GovernorError::DurationError)
+                governor.update_param(GovernorParam::Quorum(2_000)),
+                Err(GovernorError::Unauthorized)
             );
-            let result = governor.propose(accounts.django, 100, 1);
-            assert_eq!(result, Ok(()));
-            let proposal = governor.get_proposal(0).unwrap();
-            let now = governor.now();
+
+            set_sender(contract_id());
             assert_eq!(
-                proposal,
-                Proposal {
+                governor.update_config(GovernanceConfig {
+                    min_duration: MAX_DURATION + 1,
+                    ..config
+                }),
+                Err(GovernorError::InvalidParameter)
+            );
+            let events = ink::env::test::recorded_events().count();
+            assert_eq!(governor.update_config(config), Ok(()));
+            assert_eq!(governor.get_config(), config);
+            assert_eq!(ink::env::test::recorded_events().count(), events + 2);
+        }
+
+        #[ink::test]
+        fn set_governance_token_waits_for_open_proposals() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let new_token = AccountId::from([0x07; 32]);
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.set_governance_token(new_token),
+                Err(GovernorError::Unauthorized)
+            );
+            set_sender(accounts.alice);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+
+            let selector = ink::selector_bytes!("set_governance_token");
+            assert_eq!(
+                execute_self_call(&mut governor, selector, new_token.encode()),
+                Err(GovernorError::GovernanceTokenInUse)
+            );
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(governor.get_proposal(0).unwrap().eta.unwrap());
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.cancel(1), Ok(()));
+            assert_eq!(
+                execute_self_call(&mut governor, selector, new_token.encode()),
+                Ok(())
+            );
+            assert_eq!(governor.governance_token(), new_token);
+        }
+
+        #[ink::test]
+        fn upgrade_code_is_self_gated() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let code_hash = Hash::from([0x09; 32]);
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.upgrade_code(code_hash),
+                Err(GovernorError::Unauthorized)
+            );
+            assert_eq!(calls::code_hash(), None);
+
+            set_sender(accounts.alice);
+            assert_eq!(governor.migrate(), Err(GovernorError::NoMigrationPending));
+            assert_eq!(
+                execute_self_call(
+                    &mut governor,
+                    ink::selector_bytes!("upgrade_code"),
+                    code_hash.encode()
+                ),
+                Ok(())
+            );
+            assert_eq!(calls::code_hash(), Some(code_hash));
+            assert_eq!(governor.migrate(), Ok(()));
+        }
+
+        #[ink::test]
+        fn migrate_upgrades_storage_version() {
+            let mut governor = create_contract(1000);
+            assert_eq!(governor.version(), STORAGE_VERSION);
+            governor.version = 0;
+            governor.config.quorum_floor = 0;
+            governor.migration_pending = true;
+            assert_eq!(governor.migrate(), Ok(()));
+            assert_eq!(governor.version(), STORAGE_VERSION);
+            assert_eq!(governor.config.quorum_floor, governor.config.quorum);
+            assert_eq!(governor.migrate(), Err(GovernorError::NoMigrationPending));
+
+            governor.version = STORAGE_VERSION + 1;
+            governor.migration_pending = true;
+            assert_eq!(governor.migrate(), Err(GovernorError::UnsupportedVersion));
+        }
+
+        #[ink::test]
+        fn emergency_withdrawal_needs_supermajority() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 100);
+            let asset = AccountId::from([0x05; 32]);
+            token::set_asset_balance(asset, contract_id(), 50);
+            assert_eq!(governor.add_asset(asset), Ok(()));
+            let kind = ProposalKind::EmergencyWithdrawal(accounts.eve);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose_kind(kind.clone(), 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Defeated));
+            assert_eq!(governor.queue(1), Ok(()));
+            let eve_balance = get_balance(accounts.eve);
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(get_balance(accounts.eve), eve_balance + 1000);
+            assert_eq!(token::balance_of(asset, accounts.eve), 50);
+            assert_eq!(governor.treasury_balance(), 0);
+        }
+
+        #[ink::test]
+        fn blacklisted_recipients_cannot_be_paid() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            governor.set_param(&GovernorParam::Blacklist(accounts.django));
+            assert!(governor.is_blacklisted(accounts.django));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::RecipientBlacklisted)
+            );
+
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(
+                governor.execute(0),
+                Err(GovernorError::RecipientBlacklisted)
+            );
+            governor.set_param(&GovernorParam::Unblacklist(accounts.django));
+            assert_eq!(governor.execute(0), Ok(()));
+        }
+
+        #[ink::test]
+        fn members_only_gates_proposing_and_voting() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 100);
+            governor.set_param(&GovernorParam::MembersOnly(true));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::NotMember)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.add_member(accounts.bob),
+                Err(GovernorError::Unauthorized)
+            );
+            set_sender(contract_id());
+            assert_eq!(governor.add_member(accounts.alice), Ok(()));
+            assert!(governor.is_member(accounts.alice));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.vote(0, VoteType::For),
+                Err(GovernorError::NotMember)
+            );
+
+            set_sender(contract_id());
+            assert_eq!(governor.remove_member(accounts.alice), Ok(()));
+            assert!(!governor.is_member(accounts.alice));
+        }
+
+        #[ink::test]
+        fn compliance_registry_restricts_participation() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let registry = AccountId::from([0x0a; 32]);
+            governor.set_param(&GovernorParam::ComplianceRegistry(Some(registry)));
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::NotVerified)
+            );
+            set_sender(accounts.bob);
+            set_value_transferred(10);
+            assert_eq!(governor.donate(), Err(GovernorError::NotVerified));
+            set_value_transferred(0);
+
+            set_sender(accounts.alice);
+            compliance::verify(registry, accounts.alice);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+        }
+
+        #[ink::test]
+        fn council_fast_tracks_small_proposals() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.council = Some(Council {
+                members: vec![accounts.bob, accounts.charlie],
+                required: 2,
+                max_amount: 100,
+                voting_period: 1,
+            });
+            assert_eq!(
+                governor.propose(accounts.django, 100, 10, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 500, 10, Hash::default()),
+                Ok(())
+            );
+
+            assert_eq!(governor.fast_track(0), Err(GovernorError::NotCouncilMember));
+            set_sender(accounts.bob);
+            assert_eq!(governor.fast_track(1), Err(GovernorError::ProposalTooLarge));
+            assert_eq!(governor.fast_track(0), Ok(()));
+            assert_eq!(governor.fast_track(0), Err(GovernorError::AlreadySigned));
+            assert_eq!(governor.get_proposal(0).unwrap().vote_end, 10 * ONE_MINUTE);
+            set_sender(accounts.charlie);
+            assert_eq!(governor.fast_track(0), Ok(()));
+            assert_eq!(governor.get_proposal(0).unwrap().vote_end, ONE_MINUTE);
+
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Active));
+        }
+
+        #[ink::test]
+        fn tracks_set_vote_requirements() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let small_spend = Track {
+                id: 1,
+                quorum: 1_000,
+                approval_threshold: 50,
+                voting_period: 2,
+                timelock_delay: 0,
+                max_spend: Some(100),
+            };
+            governor.set_param(&GovernorParam::Track(small_spend));
+            let transfer = |amount| {
+                ProposalKind::NativeTransfer(vec![Transfer {
                     to: accounts.django,
-                    amount: 100,
-                    vote_start: 0,
-                    vote_end: now + 1 * ONE_MINUTE,
-                    executed: false,
-                }
+                    amount,
+                }])
+            };
+            assert_eq!(
+                governor.propose_on_track(2, transfer(50), Hash::default()),
+                Err(GovernorError::TrackNotFound)
             );
-            assert_eq!(governor.next_proposal_id(), 1);
+            assert_eq!(
+                governor.propose_on_track(1, transfer(150), Hash::default()),
+                Err(GovernorError::ProposalTooLarge)
+            );
+            assert_eq!(
+                governor.propose_on_track(1, transfer(50), Hash::default()),
+                Ok(())
+            );
+            let proposal = governor.get_proposal(0).unwrap();
+            assert_eq!(proposal.vote_end, 2 * ONE_MINUTE);
+            assert_eq!(proposal.track, Some(small_spend));
+
+            token::set_balance_of(accounts.alice, 0);
+            token::set_balance_of(accounts.bob, 150);
+            set_sender(accounts.bob);
+            set_now(1);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            governor.set_param(&GovernorParam::RemoveTrack(1));
+            set_now(2 * ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            let django_balance = get_balance(accounts.django);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(get_balance(accounts.django), django_balance + 50);
         }
 
         #[ink::test]
-        fn quorum_not_reached() {
+        fn objections_cancel_passed_proposals() {
+            let accounts = default_accounts();
             let mut governor = create_contract(1000);
-            let result = governor.propose(AccountId::from([0x02; 32]), 100, 1);
-            assert_eq!(result, Ok(()));
-            let execute = governor.execute(0);
-            assert_eq!(execute, Err(GovernorError::QuorumNotReached));
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 50);
+            token::set_balance_of(accounts.charlie, 100);
+            governor.objection_window = 10;
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.object(0), Ok(()));
+            assert_eq!(governor.object(0), Err(GovernorError::AlreadyObjected));
+            assert_eq!(governor.object(1), Ok(()));
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Queued));
+            set_sender(accounts.charlie);
+            assert_eq!(governor.object(0), Ok(()));
+            assert_eq!(governor.objections(0), 150);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Canceled));
+
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(1), Err(GovernorError::ObjectionWindowOpen));
+            set_now(11 * ONE_MINUTE + 1);
+            assert_eq!(
+                governor.object(1),
+                Err(GovernorError::ObjectionWindowClosed)
+            );
+            assert_eq!(governor.execute(1), Ok(()));
+
+            // A window that cannot be added to the vote end never closes.
+            governor.objection_window = u64::MAX;
+            let proposal = governor.get_proposal(1).unwrap();
+            assert!(governor.objection_window_open(&proposal));
+
+            set_sender(contract_id());
+            for param in [
+                GovernorParam::ObjectionWindow(MAX_SAFEGUARD_DELAY + 1),
+                GovernorParam::KillSwitchDelay(MAX_SAFEGUARD_DELAY + 1),
+            ] {
+                assert_eq!(
+                    governor.update_param(param),
+                    Err(GovernorError::InvalidParameter)
+                );
+            }
+            assert_eq!(
+                governor
+                    .update_param(GovernorParam::ObjectionWindow(MAX_SAFEGUARD_DELAY)),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn open_proposals_are_capped() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            governor.max_open_proposals = 2;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Ok(())
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            set_sender(accounts.charlie);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Err(GovernorError::TooManyActiveProposals)
+            );
+            assert_eq!(governor.open_proposals(), vec![0, 1]);
+
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 5, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.open_proposals(), vec![0, 2]);
+        }
+
+        #[ink::test]
+        fn sunset_dissolves_the_dao() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 700);
+            token::set_balance_of(accounts.bob, 200);
+            set_now(1);
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose_kind(ProposalKind::Sunset, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_sender(accounts.bob);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::Against), Ok(()));
+            set_sender(accounts.alice);
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 2);
+            assert_eq!(governor.get_state(0), Ok(ProposalState::Succeeded));
+            assert_eq!(governor.get_state(1), Ok(ProposalState::Defeated));
+
+            set_sender(accounts.charlie);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 2 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert!(governor.terminated());
+            assert_eq!(governor.treasury_balance(), 0);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::Terminated)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.claim_dividend(0), Ok(200));
+        }
+
+        #[ink::test]
+        fn kill_switch_freezes_executions_after_delay() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.guardian = Some(accounts.eve);
+            governor.kill_switch_delay = TIMELOCK_DELAY + 5;
+            for _ in 0..2 {
+                assert_eq!(
+                    governor.propose(accounts.django, 100, 1, Hash::default()),
+                    Ok(())
+                );
+            }
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(governor.vote(1, VoteType::For), Ok(()));
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.arm_kill_switch(), Err(GovernorError::Unauthorized));
+            set_sender(accounts.eve);
+            governor.kill_switch_delay = u64::MAX;
+            assert_eq!(
+                governor.arm_kill_switch(),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+            governor.kill_switch_delay = TIMELOCK_DELAY + 5;
+            assert_eq!(governor.arm_kill_switch(), Ok(()));
+            assert_eq!(
+                governor.disarm_kill_switch(),
+                Err(GovernorError::Unauthorized)
+            );
+
+            set_sender(accounts.alice);
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            assert_eq!(governor.queue(1), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            set_now((TIMELOCK_DELAY + 5) * ONE_MINUTE);
+            assert_eq!(governor.execute(1), Err(GovernorError::KillSwitchEngaged));
+
+            assert_eq!(governor.disarm_kill_switch(), Ok(()));
+            assert_eq!(governor.kill_switch_at(), None);
+            assert_eq!(governor.execute(1), Ok(()));
+            assert_eq!(
+                governor.disarm_kill_switch(),
+                Err(GovernorError::KillSwitchNotArmed)
+            );
+        }
+
+        #[ink::test]
+        fn administrative_messages_reject_outsiders() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let config = governor.get_config();
+            set_sender(accounts.bob);
+            let unauthorized = Err(GovernorError::Unauthorized);
+            assert_eq!(governor.transfer_admin(Some(accounts.bob)), unauthorized);
+            assert_eq!(
+                governor.grant_role(Role::Guardian, accounts.bob),
+                unauthorized
+            );
+            assert_eq!(
+                governor.revoke_role(Role::Guardian, accounts.bob),
+                unauthorized
+            );
+            assert_eq!(governor.add_asset(accounts.django), unauthorized);
+            assert_eq!(governor.remove_asset(accounts.django), unauthorized);
+            assert_eq!(governor.pause(), unauthorized);
+            assert_eq!(governor.unpause(), unauthorized);
+            assert_eq!(governor.veto(0), unauthorized);
+            assert_eq!(governor.arm_kill_switch(), unauthorized);
+            assert_eq!(governor.disarm_kill_switch(), unauthorized);
+            assert_eq!(governor.add_member(accounts.bob), unauthorized);
+            assert_eq!(governor.remove_member(accounts.bob), unauthorized);
+            assert_eq!(governor.update_config(config), unauthorized);
+            assert_eq!(
+                governor.update_param(GovernorParam::MembersOnly(true)),
+                unauthorized
+            );
+            assert_eq!(governor.set_governance_token(accounts.django), unauthorized);
+            assert_eq!(governor.upgrade_code(Hash::default()), unauthorized);
+        }
+
+        #[ink::test]
+        fn proposal_lifecycle_emits_events() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+            let vote_cast = ink::env::test::recorded_events().last().unwrap();
+            // The event signature, then the proposal id and the voter.
+            assert_eq!(vote_cast.topics.len(), 3);
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
+            let payout_sent = ink::env::test::recorded_events().nth(2).unwrap();
+            // The event signature, then the proposal id and the recipient.
+            assert_eq!(payout_sent.topics.len(), 3);
+        }
+
+        #[ink::test]
+        fn rage_quit_leaves_grants_funded() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            token::set_balance_of(accounts.bob, 100);
+            let stream = StreamPayout {
+                to: accounts.django,
+                amount: 100,
+                duration: 10,
+            };
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Stream(stream), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.treasury_balance(), 900);
+
+            set_sender(accounts.bob);
+            assert_eq!(governor.rage_quit(100), Ok(90));
+            set_sender(accounts.django);
+            set_now(ONE_MINUTE + 1 + (TIMELOCK_DELAY + 10) * ONE_MINUTE);
+            assert_eq!(governor.withdraw_stream(0), Ok(100));
+            assert_eq!(governor.treasury_balance(), 810);
+        }
+
+        #[ink::test]
+        fn self_calls_apply_through_execute() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            let config = GovernanceConfig {
+                quorum: 2_000,
+                timelock_delay: TIMELOCK_DELAY + 5,
+                ..governor.get_config()
+            };
+            assert_eq!(
+                execute_self_call(
+                    &mut governor,
+                    ink::selector_bytes!("update_config"),
+                    config.encode()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.get_config(), config);
+            assert_eq!(
+                execute_self_call(
+                    &mut governor,
+                    ink::selector_bytes!("update_param"),
+                    GovernorParam::Quorum(3_000).encode()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.get_config().quorum, 3_000);
+            assert_eq!(
+                execute_self_call(
+                    &mut governor,
+                    ink::selector_bytes!("update_param"),
+                    Vec::new()
+                ),
+                Err(GovernorError::InvalidParameter)
+            );
+            assert_eq!(
+                execute_self_call(
+                    &mut governor,
+                    ink::selector_bytes!("pause"),
+                    Vec::new()
+                ),
+                Err(GovernorError::InvalidProposalKind)
+            );
+        }
+
+        #[ink::test]
+        fn governance_disarms_kill_switch_through_execute() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.alice, 600);
+            governor.guardian = Some(accounts.eve);
+            set_sender(accounts.eve);
+            assert_eq!(governor.arm_kill_switch(), Ok(()));
+
+            set_sender(accounts.alice);
+            assert_eq!(
+                execute_self_call(
+                    &mut governor,
+                    ink::selector_bytes!("disarm_kill_switch"),
+                    Vec::new()
+                ),
+                Ok(())
+            );
+            assert_eq!(governor.kill_switch_at(), None);
+        }
+
+        #[ink::test]
+        fn rage_quit_share_does_not_wrap() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(10u128.pow(30));
+            token::set_total_supply(10u128.pow(30));
+            token::set_balance_of(accounts.bob, 10u128.pow(29));
+            set_sender(accounts.bob);
+            assert_eq!(governor.rage_quit(10u128.pow(29)), Ok(10u128.pow(29)));
+            assert_eq!(
+                Governor::mul_div(Balance::MAX, Balance::MAX, Balance::MAX),
+                Ok(Balance::MAX)
+            );
+            assert_eq!(
+                Governor::mul_div(Balance::MAX, 2, 1),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+        }
+
+        #[ink::test]
+        fn dividends_do_not_wrap_on_large_supplies() {
+            let accounts = default_accounts();
+            let unit = 10u128.pow(29);
+            let mut governor = create_contract(10 * unit);
+            token::set_total_supply(10 * unit);
+            token::set_balance_of(accounts.bob, 3 * unit);
+            token::set_balance_of(accounts.charlie, 7 * unit);
+            set_sender(accounts.charlie);
+            assert_eq!(
+                governor.propose_kind(ProposalKind::Distribute(unit), 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(governor.vote(0, VoteType::For), Ok(()));
+            set_now(ONE_MINUTE + 1);
+            assert_eq!(governor.queue(0), Ok(()));
+            set_now(ONE_MINUTE + 1 + TIMELOCK_DELAY * ONE_MINUTE);
+            assert_eq!(governor.execute(0), Ok(()));
+            assert_eq!(governor.claim_dividend(0), Ok(7 * unit / 10));
+            assert_eq!(governor.claimable(accounts.bob, 0), 3 * unit / 10);
+        }
+
+        #[ink::test]
+        fn oversized_periods_are_rejected() {
+            let accounts = default_accounts();
+            let mut governor = create_contract(1000);
+            let governance_token = AccountId::from(token::GOVERNANCE_TOKEN);
+            token::set_total_supply(1000);
+            token::set_balance_of(accounts.bob, 100);
+            governor.conviction_period = u64::MAX;
+            governor.reward_epoch = u64::MAX;
+            governor.proposal_cooldown = u64::MAX;
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Ok(())
+            );
+            assert_eq!(
+                governor.propose(accounts.django, 100, 1, Hash::default()),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+
+            set_now(1);
+            set_sender(accounts.bob);
+            assert_eq!(
+                governor.vote_with_conviction(0, VoteType::For, Conviction::Locked1x),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+            assert_eq!(token::balance_of(governance_token, accounts.bob), 100);
+            assert_eq!(governor.lock(0, MAX_VOTE_LOCK), Ok(()));
+            set_now(u64::MAX);
+            assert_eq!(governor.lock(0, 1), Err(GovernorError::ArithmeticOverflow));
+            assert_eq!(
+                governor.claim_rewards(1),
+                Err(GovernorError::ArithmeticOverflow)
+            );
+        }
+    }
+
+    /// Run against a contracts node with `cargo test --features e2e-tests`.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use callback_token::callback_token::CallbackTokenRef;
+        use ink::env::DefaultEnvironment;
+
+        type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+        async fn instantiate(
+            client: &mut ink_e2e::Client<ink_e2e::PolkadotConfig, DefaultEnvironment>,
+            call_back: bool,
+        ) -> AccountId {
+            let token = client
+                .instantiate(
+                    "callback_token",
+                    &ink_e2e::alice(),
+                    CallbackTokenRef::new(call_back),
+                    0,
+                    None,
+                )
+                .await
+                .expect("token instantiation failed")
+                .account_id;
+            let constructor = GovernorRef::new(token, 5_000, 0, 1, 60, 60, 0, 0, None);
+            client
+                .instantiate("dao", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("governor instantiation failed")
+                .account_id
+        }
+
+        // Paths are relative to the workspace root, where cargo runs rustc.
+        #[ink_e2e::test(additional_contracts = "contracts/callback-token/Cargo.toml")]
+        async fn rage_quit_callbacks_are_refused(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let governor = instantiate(&mut client, false).await;
+            let rage_quit = ink_e2e::build_message::<GovernorRef>(governor)
+                .call(|governor| governor.rage_quit(1));
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &rage_quit, 0, None)
+                .await;
+            assert_eq!(result.return_value(), Ok(0));
+
+            // The token calls `rage_quit` again while burning, which
+            // pallet-contracts refuses, failing the outer call.
+            let governor = instantiate(&mut client, true).await;
+            let rage_quit = ink_e2e::build_message::<GovernorRef>(governor)
+                .call(|governor| governor.rage_quit(1));
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &rage_quit, 0, None)
+                .await;
+            assert_eq!(
+                result.return_value(),
+                Err(GovernorError::TokenTransferFailed)
+            );
+
+            Ok(())
         }
     }
 }